@@ -0,0 +1,382 @@
+use std::fs;
+use std::io::{Read, Write};
+use crate::models::{ParseError, ParsedDataTRS};
+use crate::parsers::{Parse, ParserInterpret, TrsFormat};
+use crate::server::handlers::{self, ErrorJson, ResponseJson};
+use crate::models::ParsedData;
+use crate::rewriting::{complete, CompletionOptions, ReductionOrder};
+use crate::termination::{DependencyGraph, Kbo, Lpo, Precedence, SmtEncoding, Verdict, DEFAULT_MINIMUM};
+
+pub const EXIT_OK: i32 = 0;
+/// Ошибки разбора или недоказанная завершаемость.
+pub const EXIT_FAILURE: i32 = 1;
+/// Неверные аргументы командной строки или ошибка чтения файла.
+pub const EXIT_USAGE: i32 = 2;
+
+const USAGE: &str = "\
+Использование:
+  tofl-gpt-parser [serve]                                           запуск HTTP-сервера
+  tofl-gpt-parser parse-trs [--json] [--tpdb] [--warnings] [FILE]   разбор TRS
+  tofl-gpt-parser parse-interp [--json] [--tpdb] [--warnings] --trs FILE [FILE]
+                                                                    разбор интерпретации
+  tofl-gpt-parser check [--json] [--tpdb] [--warnings] --trs FILE [FILE]
+                                                                    проверка завершаемости
+  tofl-gpt-parser export-tpdb [FILE]                                перевод TRS в формат TPDB
+  tofl-gpt-parser export-smt [--tpdb] [--interp FILE] [FILE]        условия завершаемости в SMT-LIB 2
+  tofl-gpt-parser complete [--json] [--tpdb] [--precedence P | --kbo FILE] [FILE]
+                                                                    пополнение по Кнуту–Бендиксу
+  tofl-gpt-parser dependency-graph [--json] [--tpdb] [FILE]         граф зависимостей в формате DOT
+
+Если FILE не указан или равен '-', данные читаются из stdin.
+С флагом --tpdb TRS читается в формате TPDB: (VAR x y) (RULES f(x,y) -> g(x)).
+С флагом --warnings сообщается о повторяющихся правилах и правилах с совпадающими частями.
+Для export-smt символы, не заданные в --interp, получают линейный шаблон с неизвестными коэффициентами.
+Для complete правила TRS считаются уравнениями; порядок — LPO с прецедентностью P (\"f > g > a\")
+или KBO, заданный в файле --kbo.";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    ParseTrs,
+    ParseInterpret,
+    Check,
+    ExportTpdb,
+    ExportSmt,
+    Complete,
+    DependencyGraph,
+}
+
+#[derive(Debug)]
+struct Options {
+    command: Command,
+    json: bool,
+    warnings: bool,
+    format: TrsFormat,
+    trs: Option<String>,
+    interp: Option<String>,
+    precedence: Option<String>,
+    kbo: Option<String>,
+    input: Option<String>,
+}
+
+/// Запуск без аргументов или с `serve` поднимает HTTP-сервер.
+pub fn is_server_mode(args: &[String]) -> bool {
+    args.is_empty() || args[0] == "serve"
+}
+
+/// Выполняет консольную команду и возвращает код завершения процесса.
+pub fn run(args: &[String], stdin: &mut dyn Read, stdout: &mut dyn Write, stderr: &mut dyn Write) -> i32 {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            let _ = writeln!(stderr, "{}\n\n{}", e, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+
+    let mut stdin = Some(stdin);
+    let inputs = read_input(options.input.as_deref(), &mut stdin).and_then(|input| {
+        match options.command {
+            Command::ParseTrs | Command::ExportTpdb | Command::DependencyGraph => Ok((input, String::new())),
+            Command::ExportSmt => match options.interp.as_deref() {
+                Some(path) => Ok((input, read_input(Some(path), &mut stdin)?)),
+                None => Ok((input, String::new())),
+            },
+            Command::Complete => match options.kbo.as_deref() {
+                Some(path) => Ok((input, read_input(Some(path), &mut stdin)?)),
+                None => Ok((input, String::new())),
+            },
+            _ => Ok((input, read_input(options.trs.as_deref(), &mut stdin)?)),
+        }
+    });
+    let (input, trs) = match inputs {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            let _ = writeln!(stderr, "{}", e);
+            return EXIT_USAGE;
+        }
+    };
+
+    let result = match options.command {
+        Command::ParseTrs => handlers::parse_trs(&input, options.format)
+            .map(|(trs, _)| {
+                let warnings = if options.warnings { trs.check_well_formed(true) } else { Vec::new() };
+                print_warnings("Предупреждения в TRS", &warnings, options.json, stderr);
+                print_trs(&trs, &warnings, options.json, stdout)
+            })
+            .map_err(trs_errors),
+        Command::ExportTpdb => handlers::parse_trs(&input, options.format)
+            .map(|(trs, _)| {
+                let _ = write!(stdout, "{}", trs.to_tpdb());
+                EXIT_OK
+            })
+            .map_err(trs_errors),
+        Command::ExportSmt => export_smt(&input, options.format, options.interp.as_ref().map(|_| trs.as_str()))
+            .map(|script| {
+                let _ = write!(stdout, "{}", script);
+                EXIT_OK
+            }),
+        Command::ParseInterpret => handlers::process(&trs, options.format, &input, DEFAULT_MINIMUM, options.warnings)
+            .map(|res| {
+                print_warnings("Предупреждения в TRS", &res.error_trs, options.json, stderr);
+                print_warnings("Предупреждения в интерпретации", &res.diagnostics, options.json, stderr);
+                print_interpretation(&res, options.json, stdout)
+            }),
+        Command::Check => handlers::process(&trs, options.format, &input, DEFAULT_MINIMUM, options.warnings)
+            .map(|res| {
+                print_warnings("Предупреждения в TRS", &res.error_trs, options.json, stderr);
+                print_warnings("Предупреждения в интерпретации", &res.diagnostics, options.json, stderr);
+                print_check(&res, options.json, stdout)
+            }),
+        Command::Complete => run_completion(&input, &options, &trs, stdout, stderr),
+        Command::DependencyGraph => handlers::parse_trs(&input, options.format)
+            .map(|(trs, _)| {
+                let graph = DependencyGraph::new(&trs);
+                if options.json {
+                    let _ = writeln!(stdout, "{}", serde_json::to_string(&graph).unwrap_or_default());
+                } else {
+                    let _ = write!(stdout, "{}", graph.to_dot());
+                }
+                EXIT_OK
+            })
+            .map_err(trs_errors),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            print_errors(&err, options.json, stdout, stderr);
+            EXIT_FAILURE
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let command = match args.first().map(String::as_str) {
+        Some("parse-trs") => Command::ParseTrs,
+        Some("parse-interp") => Command::ParseInterpret,
+        Some("check") => Command::Check,
+        Some("export-tpdb") => Command::ExportTpdb,
+        Some("export-smt") => Command::ExportSmt,
+        Some("complete") => Command::Complete,
+        Some("dependency-graph") => Command::DependencyGraph,
+        Some(other) => return Err(format!("Неизвестная команда: {}", other)),
+        None => return Err("Не указана команда".to_string()),
+    };
+
+    let mut options = Options { command, json: false, warnings: false, format: TrsFormat::Course, trs: None, interp: None, precedence: None, kbo: None, input: None };
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--warnings" => options.warnings = true,
+            "--tpdb" => options.format = TrsFormat::Tpdb,
+            "--trs" => match rest.next() {
+                Some(file) => options.trs = Some(file.clone()),
+                None => return Err("После --trs ожидалось имя файла".to_string()),
+            },
+            "--interp" => match rest.next() {
+                Some(file) => options.interp = Some(file.clone()),
+                None => return Err("После --interp ожидалось имя файла".to_string()),
+            },
+            "--precedence" => match rest.next() {
+                Some(precedence) => options.precedence = Some(precedence.clone()),
+                None => return Err("После --precedence ожидалась прецедентность".to_string()),
+            },
+            "--kbo" => match rest.next() {
+                Some(file) => options.kbo = Some(file.clone()),
+                None => return Err("После --kbo ожидалось имя файла".to_string()),
+            },
+            _ if arg.starts_with("--") => return Err(format!("Неизвестный параметр: {}", arg)),
+            _ if options.input.is_none() => options.input = Some(arg.clone()),
+            _ => return Err(format!("Лишний аргумент: {}", arg)),
+        }
+    }
+
+    if options.warnings && !matches!(options.command, Command::ParseTrs | Command::ParseInterpret | Command::Check) {
+        return Err("Параметр --warnings используется только командами parse-trs, parse-interp и check".to_string());
+    }
+    if options.interp.is_some() && options.command != Command::ExportSmt {
+        return Err("Параметр --interp используется только командой export-smt".to_string());
+    }
+    if (options.precedence.is_some() || options.kbo.is_some()) && options.command != Command::Complete {
+        return Err("Параметры --precedence и --kbo используются только командой complete".to_string());
+    }
+    if options.precedence.is_some() && options.kbo.is_some() {
+        return Err("Укажите только один порядок: --precedence или --kbo".to_string());
+    }
+    match (options.command, &options.trs) {
+        (Command::ParseTrs | Command::ExportTpdb | Command::ExportSmt | Command::Complete | Command::DependencyGraph, Some(_)) => Err("Параметр --trs используется только командами parse-interp и check".to_string()),
+        (Command::ParseInterpret | Command::Check, None) => Err("Не указан файл TRS (--trs FILE)".to_string()),
+        _ => Ok(options),
+    }
+}
+
+/// Читает файл, а если имя не указано или равно `-` — stdin (не более одного раза).
+fn read_input(path: Option<&str>, stdin: &mut Option<&mut dyn Read>) -> Result<String, String> {
+    match path {
+        None | Some("-") => {
+            let reader = stdin.take().ok_or("stdin можно использовать только для одного входного файла")?;
+            let mut input = String::new();
+            reader.read_to_string(&mut input).map_err(|e| format!("Не удалось прочитать stdin: {}", e))?;
+            Ok(input)
+        }
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("Не удалось прочитать файл {}: {}", path, e)),
+    }
+}
+
+/// Интерпретация для export-smt может быть неполной: недостающие символы ищет решатель.
+fn export_smt(trs: &str, format: TrsFormat, interpretation: Option<&str>) -> Result<String, ErrorJson> {
+    let (trs, model) = handlers::parse_trs(trs, format).map_err(trs_errors)?;
+    let known = match interpretation {
+        Some(input) => match ParserInterpret::partial(input, model).parse() {
+            Ok(ParsedData::Interpret(interpret)) => interpret,
+            Ok(_) => Vec::new(),
+            Err(e) => return Err(ErrorJson { error_trs: Vec::new(), error_interpretation: e, error_termination: Vec::new() }),
+        },
+        None => Vec::new(),
+    };
+    match SmtEncoding::new(&trs, &known) {
+        Ok(encoding) => Ok(encoding.to_smt_lib()),
+        Err(e) => Err(ErrorJson { error_trs: Vec::new(), error_interpretation: Vec::new(), error_termination: vec![e] }),
+    }
+}
+
+/// Без `--kbo` используется LPO с прецедентностью из `--precedence` (по умолчанию пустой).
+fn run_completion(input: &str, options: &Options, kbo: &str, stdout: &mut dyn Write, stderr: &mut dyn Write) -> Result<i32, ErrorJson> {
+    let (trs, _) = handlers::parse_trs(input, options.format).map_err(trs_errors)?;
+    let order = match options.kbo {
+        Some(_) => Kbo::parse(kbo).map(ReductionOrder::Kbo),
+        None => Precedence::parse(options.precedence.as_deref().unwrap_or("")).map(|p| ReductionOrder::Lpo(Lpo::new(p))),
+    };
+    let order = order.map_err(|e| ErrorJson {
+        error_trs: Vec::new(),
+        error_interpretation: Vec::new(),
+        error_termination: vec![e.message()],
+    })?;
+
+    match complete(&trs, &order, &CompletionOptions::default()) {
+        Ok(completed) => Ok(print_trs(&completed, &[], options.json, stdout)),
+        Err(failure) => {
+            if options.json {
+                let _ = writeln!(stdout, "{}", serde_json::to_string(&failure).unwrap_or_default());
+                return Ok(EXIT_FAILURE);
+            }
+            let _ = writeln!(stderr, "{}", failure.reason);
+            if !failure.rules.is_empty() {
+                let _ = writeln!(stderr, "Построенные правила:");
+            }
+            for rule in &failure.rules {
+                let _ = writeln!(stderr, "  {} = {}", rule.left, rule.right);
+            }
+            Ok(EXIT_FAILURE)
+        }
+    }
+}
+
+fn trs_errors(errors: Vec<ParseError>) -> ErrorJson {
+    ErrorJson { error_trs: errors, error_interpretation: Vec::new(), error_termination: Vec::new() }
+}
+
+fn print_trs(trs: &ParsedDataTRS, warnings: &[ParseError], json: bool, stdout: &mut dyn Write) -> i32 {
+    if json {
+        let mut payload = serde_json::json!({ "json_TRS": trs.rules });
+        if !warnings.is_empty() {
+            payload["error_trs"] = serde_json::json!(warnings);
+        }
+        let _ = writeln!(stdout, "{}", payload);
+        return EXIT_OK;
+    }
+    let mut variables: Vec<&str> = trs.variables.iter().map(String::as_str).collect();
+    variables.sort();
+    let _ = writeln!(stdout, "Переменные: {}", variables.join(", "));
+    let _ = writeln!(stdout, "Правила:");
+    for rule in &trs.rules {
+        let _ = writeln!(stdout, "  {} = {}", rule.left, rule.right);
+    }
+    EXIT_OK
+}
+
+fn print_interpretation(res: &ResponseJson, json: bool, stdout: &mut dyn Write) -> i32 {
+    if json {
+        let mut payload = serde_json::json!({ "json_interpret": res.json_interpret });
+        if !res.error_trs.is_empty() {
+            payload["error_trs"] = serde_json::json!(res.error_trs);
+        }
+        if !res.diagnostics.is_empty() {
+            payload["diagnostics"] = serde_json::json!(res.diagnostics);
+        }
+        let _ = writeln!(stdout, "{}", payload);
+        return EXIT_OK;
+    }
+    for function in &res.json_interpret.functions {
+        if function.variables.is_empty() {
+            let _ = writeln!(stdout, "{} = {}", function.name, function.definition());
+        } else {
+            let _ = writeln!(stdout, "{}({}) = {}", function.name, function.variables.join(", "), function.definition());
+        }
+    }
+    EXIT_OK
+}
+
+/// Код завершения `EXIT_FAILURE`, если хотя бы для одного правила `[l] > [r]` не доказано.
+fn print_check(res: &ResponseJson, json: bool, stdout: &mut dyn Write) -> i32 {
+    let proved = res.termination.iter().all(|verdict| verdict.verdict == Verdict::Proved);
+    let code = if proved { EXIT_OK } else { EXIT_FAILURE };
+    if json {
+        let _ = writeln!(stdout, "{}", serde_json::to_string(res).unwrap_or_default());
+        return code;
+    }
+
+    for verdict in &res.termination {
+        let rule = &res.json_trs[verdict.rule];
+        let status = match &verdict.verdict {
+            Verdict::Proved => "доказано".to_string(),
+            Verdict::NotProved => "не доказано".to_string(),
+            Verdict::Counterexample { assignment, left, right } => {
+                let values: Vec<String> = assignment.iter().map(|(v, value)| format!("{} = {}", v, value)).collect();
+                format!("контрпример {} ([l] = {}, [r] = {})", values.join(", "), left, right)
+            }
+        };
+        let _ = writeln!(stdout, "Правило {}: {} = {}: {} > {}: {}",
+                         verdict.rule, rule.left, rule.right, verdict.left, verdict.right, status);
+    }
+    if proved {
+        let _ = writeln!(stdout, "Завершаемость доказана");
+    } else {
+        let _ = writeln!(stdout, "Завершаемость не доказана");
+    }
+    code
+}
+
+/// Предупреждения выводятся в stderr; в режиме `--json` они входят в ответ команды.
+fn print_warnings(title: &str, warnings: &[ParseError], json: bool, stderr: &mut dyn Write) {
+    if json || warnings.is_empty() {
+        return;
+    }
+    let _ = writeln!(stderr, "{}:", title);
+    for e in warnings {
+        let _ = writeln!(stderr, "  {}", e);
+    }
+}
+
+fn print_errors(err: &ErrorJson, json: bool, stdout: &mut dyn Write, stderr: &mut dyn Write) {
+    if json {
+        let _ = writeln!(stdout, "{}", serde_json::to_string(err).unwrap_or_default());
+        return;
+    }
+    let sections: [(&str, &[ParseError]); 2] = [
+        ("Ошибки в TRS", &err.error_trs),
+        ("Ошибки в интерпретации", &err.error_interpretation),
+    ];
+    for (title, errors) in sections {
+        if !errors.is_empty() {
+            let _ = writeln!(stderr, "{}:", title);
+        }
+        for e in errors {
+            let _ = writeln!(stderr, "  {}", e);
+        }
+    }
+    for e in &err.error_termination {
+        let _ = writeln!(stderr, "{}", e);
+    }
+}
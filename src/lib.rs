@@ -1,3 +1,6 @@
 pub mod server;
 pub mod parsers;
 pub mod models;
+pub mod rewriting;
+pub mod termination;
+pub mod cli;
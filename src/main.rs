@@ -1,6 +1,14 @@
-use tofl_gpt_parser;
+use std::io;
+use std::process;
+use tofl_gpt_parser::cli;
 
 fn main() {
-    println!("Запуск сервера парсера!");
-    tofl_gpt_parser::server::start_server()
-}
\ No newline at end of file
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if cli::is_server_mode(&args) {
+        println!("Запуск сервера парсера!");
+        tofl_gpt_parser::server::start_server();
+        return;
+    }
+    let code = cli::run(&args, &mut io::stdin(), &mut io::stdout(), &mut io::stderr());
+    process::exit(code)
+}
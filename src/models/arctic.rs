@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Слагаемое арктического полинома: `coefficient + d1 * x1 + ... + dn * xn`
+/// (в арктическом полукольце сложение — это умножение, `d * x` — степень).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+pub struct ArcticMonomial {
+    pub coefficient: i64,
+    pub variables: BTreeMap<String, u32>,
+}
+
+/// Максимум слагаемых; пустой максимум — `-inf`. В нормальной форме нет слагаемых,
+/// которые не больше другого слагаемого ни при каких натуральных значениях переменных.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(serde::Serialize)]
+pub struct ArcticPolynomial {
+    pub monomials: Vec<ArcticMonomial>,
+}
+
+impl ArcticMonomial {
+    pub fn constant(coefficient: i64) -> Self {
+        ArcticMonomial { coefficient, variables: BTreeMap::new() }
+    }
+
+    /// `self` не меньше `other` при любых натуральных значениях переменных.
+    pub fn dominates(&self, other: &ArcticMonomial) -> bool {
+        self.coefficient >= other.coefficient
+            && other.variables.iter().all(|(v, d)| self.variables.get(v).is_some_and(|own| own >= d))
+    }
+
+    /// `None` при переполнении.
+    fn plus(&self, other: &ArcticMonomial) -> Option<ArcticMonomial> {
+        let mut result = ArcticMonomial::constant(self.coefficient.checked_add(other.coefficient)?);
+        result.variables = self.variables.clone();
+        for (variable, degree) in &other.variables {
+            let own = result.variables.entry(variable.clone()).or_insert(0);
+            *own = own.checked_add(*degree)?;
+        }
+        Some(result)
+    }
+
+    pub fn evaluate(&self, values: &HashMap<String, i64>) -> Option<i64> {
+        self.variables.iter().try_fold(self.coefficient, |acc, (variable, degree)| {
+            acc.checked_add(values.get(variable)?.checked_mul(*degree as i64)?)
+        })
+    }
+}
+
+impl ArcticPolynomial {
+    pub fn neg_infinity() -> Self {
+        ArcticPolynomial { monomials: Vec::new() }
+    }
+
+    pub fn constant(value: i64) -> Self {
+        ArcticPolynomial { monomials: vec![ArcticMonomial::constant(value)] }
+    }
+
+    pub fn variable(name: &str) -> Self {
+        let mut monomial = ArcticMonomial::constant(0);
+        monomial.variables.insert(name.to_string(), 1);
+        ArcticPolynomial { monomials: vec![monomial] }
+    }
+
+    pub fn from_monomials(monomials: Vec<ArcticMonomial>) -> Self {
+        let mut result: Vec<ArcticMonomial> = Vec::new();
+        for monomial in monomials {
+            if result.iter().any(|kept| kept.dominates(&monomial)) {
+                continue;
+            }
+            result.retain(|kept| !monomial.dominates(kept));
+            result.push(monomial);
+        }
+        result.sort_by(|a, b| a.variables.cmp(&b.variables).then(a.coefficient.cmp(&b.coefficient)));
+        ArcticPolynomial { monomials: result }
+    }
+
+    pub fn is_neg_infinity(&self) -> bool {
+        self.monomials.is_empty()
+    }
+
+    /// Арктическое сложение — максимум.
+    pub fn max(&self, other: &ArcticPolynomial) -> ArcticPolynomial {
+        ArcticPolynomial::from_monomials(self.monomials.iter().chain(&other.monomials).cloned().collect())
+    }
+
+    /// Арктическое умножение — обычное сложение, распределяется по максимумам;
+    /// `None` при переполнении.
+    pub fn plus(&self, other: &ArcticPolynomial) -> Option<ArcticPolynomial> {
+        let mut monomials = Vec::new();
+        for left in &self.monomials {
+            for right in &other.monomials {
+                monomials.push(left.plus(right)?);
+            }
+        }
+        Some(ArcticPolynomial::from_monomials(monomials))
+    }
+
+    pub fn compose(&self, substitution: &HashMap<String, ArcticPolynomial>) -> Option<ArcticPolynomial> {
+        let mut result = ArcticPolynomial::neg_infinity();
+        for monomial in &self.monomials {
+            let mut sum = ArcticPolynomial::constant(monomial.coefficient);
+            for (variable, degree) in &monomial.variables {
+                let base = match substitution.get(variable) {
+                    Some(polynomial) => polynomial.clone(),
+                    None => ArcticPolynomial::variable(variable),
+                };
+                for _ in 0..*degree {
+                    sum = sum.plus(&base)?;
+                }
+            }
+            result = result.max(&sum);
+        }
+        Some(result)
+    }
+
+    /// Значение при заданных значениях переменных: `Some(None)` — `-inf`;
+    /// `None`, если какой-то переменной нет в `values` или произошло переполнение.
+    pub fn evaluate(&self, values: &HashMap<String, i64>) -> Option<Option<i64>> {
+        let mut result = None;
+        for monomial in &self.monomials {
+            let value = monomial.evaluate(values)?;
+            result = Some(result.map_or(value, |current: i64| current.max(value)));
+        }
+        Some(result)
+    }
+}
+
+impl fmt::Display for ArcticMonomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        for (variable, degree) in &self.variables {
+            for _ in 0..*degree {
+                parts.push(variable.clone());
+            }
+        }
+        if self.coefficient != 0 || parts.is_empty() {
+            parts.push(self.coefficient.to_string());
+        }
+        write!(f, "{}", parts.join(" + "))
+    }
+}
+
+impl fmt::Display for ArcticPolynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.monomials.len() {
+            0 => write!(f, "-inf"),
+            1 => write!(f, "{}", self.monomials[0]),
+            _ => {
+                let monomials: Vec<String> = self.monomials.iter().map(|m| m.to_string()).collect();
+                write!(f, "max({})", monomials.join(", "))
+            }
+        }
+    }
+}
@@ -1,45 +1,170 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use crate::models::{ArcticPolynomial, Expression, Polynomial};
+use crate::models::parse_error::{ParseError, ParseErrorKind};
 
 #[derive(Debug)]
 pub struct Parser {
     input: Vec<char>,
     pos: u32,
+    byte_pos: u32,
     line: u32,
     pos_in_line: u32,
-    prev_line: u32,
-    prev_pos_in_line: u32,
-    errors: Vec<String>,
+    prev: Position,
+    last_end: Position,
+    errors: Vec<ParseError>,
+    comments: bool,
+}
+
+/// Позиция во входных данных: строка (с 1), позиция в строке (как в сообщениях об ошибках)
+/// и смещение в байтах от начала входа.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(serde::Serialize)]
+pub struct Position {
+    pub line: u32,
+    pub column: u32,
+    pub offset: u32,
+}
+
+/// Фрагмент входных данных `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(serde::Serialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
 }
 
 #[derive(Debug)]
 pub enum ParsedData {
     Interpret(ParsedDataInterpret),
     TRS(ParsedDataTRS),
+    Matrix(ParsedDataMatrix),
+    Arctic(ParsedDataArctic),
 }
 
 pub type ParsedDataInterpret = Vec<ParsedInterpretFunction>;
 
-#[derive(Debug)]
+pub type ParsedDataMatrix = Vec<MatrixInterpretFunction>;
+
+pub type ParsedDataArctic = Vec<ArcticInterpretFunction>;
+
+#[derive(Debug, Clone)]
 #[derive(serde::Serialize)]
 pub struct ParsedInterpretFunction {
-    pub(crate) name: String,
-    pub(crate) variables: Vec<String>,
-    pub(crate) expression: String,
+    pub name: String,
+    pub variables: Vec<String>,
+    pub expression: String,
+    pub polynomial: Polynomial,
+    /// Интерпретация с max или min, которая не сводится к полиному; `polynomial` тогда нулевой.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub piecewise: Option<Expression>,
+    pub span: Span,
 }
 
-#[derive(Debug)]
+impl ParsedInterpretFunction {
+    pub fn definition(&self) -> Expression {
+        match &self.piecewise {
+            Some(expression) => expression.clone(),
+            None => Expression::Polynomial(self.polynomial.clone()),
+        }
+    }
+}
+
+pub type Vector = Vec<i64>;
+
+/// Квадратная матрица, хранящаяся по строкам.
+pub type SquareMatrix = Vec<Vector>;
+
+/// Матричная интерпретация символа над векторами натуральных чисел:
+/// `[f](x1, ..., xn) = constant + M1 * x1 + ... + Mn * xn`.
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct MatrixInterpretFunction {
+    pub name: String,
+    pub variables: Vec<String>,
+    pub constant: Vector,
+    /// Матрицы аргументов в порядке `variables`.
+    pub matrices: Vec<SquareMatrix>,
+    pub span: Span,
+}
+
+/// Интерпретация символа в арктическом полукольце (max, +) с `-inf`.
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct ArcticInterpretFunction {
+    pub name: String,
+    pub variables: Vec<String>,
+    pub expression: String,
+    pub polynomial: ArcticPolynomial,
+    pub span: Span,
+}
+
+#[derive(Debug, Default)]
 pub struct ParsedDataTRS {
     pub rules: Vec<Rule>,
-    pub variables: HashSet<char>,
-    pub constants: HashSet<char>,
-    pub functions: HashMap<char, i32>,
+    pub variables: HashSet<String>,
+    pub constants: HashSet<String>,
+    pub functions: HashMap<String, i32>,
+}
+
+impl ParsedDataTRS {
+    /// Запись TRS в формате TPDB: `(VAR x y) (RULES f(x, y) -> g(x))`.
+    pub fn to_tpdb(&self) -> String {
+        let mut variables: Vec<&str> = self.variables.iter().map(String::as_str).collect();
+        variables.sort();
+
+        let mut result = String::new();
+        if !variables.is_empty() {
+            result.push_str(&format!("(VAR {})\n", variables.join(" ")));
+        }
+        result.push_str("(RULES\n");
+        for rule in &self.rules {
+            result.push_str(&format!("  {} -> {}\n", rule.left, rule.right));
+        }
+        result.push_str(")\n");
+        result
+    }
+
+    /// Проверка корректности правил: левая часть не может быть переменной (фатальная ошибка).
+    /// При `warnings` также отмечаются повторяющиеся правила и правила с совпадающими частями —
+    /// это нефатальные предупреждения.
+    pub fn check_well_formed(&self, warnings: bool) -> Vec<ParseError> {
+        let mut errors = Vec::new();
+        let mut seen: HashMap<(&Term, &Term), &Rule> = HashMap::new();
+        for rule in &self.rules {
+            if rule.left.is_variable(&self.variables) {
+                errors.push(ParseError::new(ParseErrorKind::VariableLeftSide, rule.left.span).symbol(&rule.left.value));
+            }
+            if !warnings {
+                continue;
+            }
+            let text = format!("{} = {}", rule.left, rule.right);
+            if rule.left == rule.right {
+                errors.push(ParseError::new(ParseErrorKind::IdenticalSides, rule.span).symbol(&text).non_fatal());
+            }
+            match seen.get(&(&rule.left, &rule.right)) {
+                Some(first) => {
+                    let e = ParseError::new(ParseErrorKind::DuplicateRule, rule.span)
+                        .symbol(&text)
+                        .related_line(first.span.start.line)
+                        .non_fatal();
+                    errors.push(e);
+                }
+                None => {
+                    seen.insert((&rule.left, &rule.right), rule);
+                }
+            }
+        }
+        errors
+    }
 }
 
 #[derive(Debug)]
 pub struct Model {
-    pub variables: HashSet<char>,
-    pub constants: HashSet<char>,
-    pub functions: HashMap<char, i32>,
+    pub variables: HashSet<String>,
+    pub constants: HashSet<String>,
+    pub functions: HashMap<String, i32>,
 }
 
 #[derive(Debug)]
@@ -48,6 +173,7 @@ pub struct Model {
 pub struct Rule {
     pub left: Term,
     pub right: Term,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -55,6 +181,109 @@ pub struct Rule {
 pub struct Term {
     pub value: String,
     pub childs: Vec<Term>,
+    pub span: Span,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Span { start, end }
+    }
+}
+
+impl Rule {
+    /// Переменные правила (из обеих частей); `variables` — объявленные переменные TRS.
+    pub fn variables(&self, variables: &HashSet<String>) -> BTreeSet<String> {
+        let mut result = self.left.variables(variables);
+        result.extend(self.right.variables(variables));
+        result
+    }
+
+    /// Переменные правой части, не входящие в левую.
+    pub fn unbound_variables(&self, variables: &HashSet<String>) -> BTreeSet<String> {
+        let left = self.left.variables(variables);
+        self.right.variables(variables).difference(&left).cloned().collect()
+    }
+}
+
+impl Term {
+    pub fn new(value: &str, childs: Vec<Term>) -> Self {
+        Term { value: value.to_string(), childs, span: Span::default() }
+    }
+
+    pub fn is_variable(&self, variables: &HashSet<String>) -> bool {
+        self.childs.is_empty() && variables.contains(&self.value)
+    }
+
+    pub fn variables(&self, variables: &HashSet<String>) -> BTreeSet<String> {
+        match self.is_variable(variables) {
+            true => BTreeSet::from([self.value.clone()]),
+            false => self.childs.iter().flat_map(|c| c.variables(variables)).collect(),
+        }
+    }
+
+    /// Подтерм в позиции `position` (последовательность номеров аргументов, с 0).
+    pub fn subterm(&self, position: &[usize]) -> Option<&Term> {
+        match position.split_first() {
+            None => Some(self),
+            Some((i, rest)) => self.childs.get(*i)?.subterm(rest),
+        }
+    }
+
+    /// Копия терма, в которой подтерм в позиции `position` заменён на `replacement`.
+    pub fn replace(&self, position: &[usize], replacement: Term) -> Term {
+        match position.split_first() {
+            None => replacement,
+            Some((i, rest)) => {
+                let mut term = self.clone();
+                term.childs[*i] = self.childs[*i].replace(rest, replacement);
+                term
+            }
+        }
+    }
+
+    /// Все позиции терма в порядке обхода сверху вниз, слева направо.
+    pub fn positions(&self) -> Vec<Vec<usize>> {
+        let mut positions = vec![Vec::new()];
+        for (i, child) in self.childs.iter().enumerate() {
+            for mut position in child.positions() {
+                position.insert(0, i);
+                positions.push(position);
+            }
+        }
+        positions
+    }
+}
+
+// Положение в исходном тексте не влияет на равенство термов
+impl PartialEq for Term {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.childs == other.childs
+    }
+}
+
+impl Eq for Term {}
+
+impl Hash for Term {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.childs.hash(state);
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)?;
+        if !self.childs.is_empty() {
+            let childs: Vec<String> = self.childs.iter().map(|c| c.to_string()).collect();
+            write!(f, "({})", childs.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Сериализация терма строкой (`f(x, g(y))`) вместо дерева.
+pub(crate) fn serialize_term<S: serde::Serializer>(term: &Term, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(term)
 }
 
 pub enum Types {
@@ -65,7 +294,7 @@ pub enum Types {
 }
 
 impl Types {
-    fn as_text(&self) -> &str {
+    pub fn as_text(&self) -> &str {
         match self {
             Types::CONSTANT => "константа",
             Types::VARIABLE => "переменная",
@@ -80,88 +309,233 @@ impl Parser {
         Parser {
             input: input.chars().collect(),
             pos: 0,
+            byte_pos: 0,
             line: 1,
             pos_in_line: 0,
-            prev_pos_in_line: 0,
-            prev_line: 0,
+            prev: Position { line: 0, column: 0, offset: 0 },
+            last_end: Position { line: 1, column: 0, offset: 0 },
             errors: Vec::new(),
+            comments: true,
         }
     }
 
+    /// Разбор без комментариев: `#`, `--` и `/*` считаются обычными символами (формат TPDB).
+    pub fn without_comments(input: &str) -> Self {
+        Parser { comments: false, ..Parser::new(input) }
+    }
+
     fn advance(&mut self) {
         //ignore spaces
-        while self.pos < self.input.len() as u32 {
-            let current = self.input[self.pos as usize];
-            self.pos += 1;
-
-            match current {
-                ' ' | '\t' => {
-                    self.pos_in_line += 1;
-                }
-                '\r' => {
-                    self.pos_in_line = 0;
-                    break;
-                }
-                '\n' => {
-                    self.line += 1;
-                    self.pos_in_line = 0;
-                    break;
-                }
-                _ => {
-                    self.pos_in_line += 1;
-                    break;
-                }
+        while let Some(current) = self.step() {
+            if current != ' ' && current != '\t' {
+                break;
             }
         }
     }
 
-    pub fn peek(&mut self) -> Result<char, String> {
-        while self.pos < self.input.len() as u32 {
-            let current = self.input[self.pos as usize];
+    /// Считывает ровно один символ, обновляя номер строки и позицию в ней.
+    fn step(&mut self) -> Option<char> {
+        let current = *self.input.get(self.pos as usize)?;
+        self.pos += 1;
+        self.byte_pos += current.len_utf8() as u32;
+        match current {
+            '\r' => self.pos_in_line = 0,
+            '\n' => {
+                self.line += 1;
+                self.pos_in_line = 0;
+            }
+            _ => self.pos_in_line += 1,
+        }
+        Some(current)
+    }
 
+    /// Пропускает пробелы, табуляции и комментарии; переводы строк остаются.
+    pub fn peek(&mut self) -> Result<char, ParseError> {
+        while let Some(&current) = self.input.get(self.pos as usize) {
             if current == ' ' || current == '\t' {
-                self.pos += 1;
-                self.pos_in_line += 1;
+                self.step();
+            } else if self.comments && self.rest_starts_with("/*") {
+                self.skip_block_comment();
+            } else if self.comments && (current == '#' || self.at_dash_comment()) {
+                // Комментарий до конца строки
+                while !matches!(self.input.get(self.pos as usize), None | Some('\n') | Some('\r')) {
+                    self.step();
+                }
             } else {
                 return Ok(current);
             }
         }
-        Err(format!("Unexpected EOF"))
+        Err(ParseError::new(ParseErrorKind::UnexpectedEof, self.point_span()))
+    }
+
+    /// Комментарий `/* ... */`, в том числе на несколько строк.
+    fn skip_block_comment(&mut self) {
+        let start = self.position();
+        self.step();
+        self.step();
+        while !self.rest_starts_with("*/") {
+            if self.step().is_none() {
+                // Остаток входа поглощён комментарием, поэтому ошибка фатальная
+                let e = ParseError::new(ParseErrorKind::UnterminatedComment, Span::new(start, self.position()));
+                self.add_fatal_error(e);
+                return;
+            }
+        }
+        self.step();
+        self.step();
+    }
+
+    /// `--` начинает комментарий только в начале строки или после пробела:
+    /// `x--1` остаётся вычитанием и приводит к ошибке разбора, а не обрезает строку.
+    fn at_dash_comment(&self) -> bool {
+        let after_space = match self.pos.checked_sub(1) {
+            Some(previous) => self.input[previous as usize].is_whitespace(),
+            None => true,
+        };
+        after_space && self.rest_starts_with("--")
+    }
+
+    fn rest_starts_with(&self, prefix: &str) -> bool {
+        let mut rest = self.input[self.pos as usize..].iter();
+        prefix.chars().all(|c| rest.next() == Some(&c))
     }
 
-    pub fn peek_without_skipping(&mut self) -> Result<char, String> {
+    pub fn peek_without_skipping(&mut self) -> Result<char, ParseError> {
         if self.pos < self.input.len() as u32 {
             Ok(self.input[self.pos as usize])
         } else {
-            Err(format!("Unexpected EOF"))
+            Err(ParseError::new(ParseErrorKind::UnexpectedEof, self.point_span()))
         }
     }
 
-    pub fn next(&mut self) -> Result<char, String> {
-        self.prev_pos_in_line = self.pos_in_line;
-        self.prev_line = self.line;
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<char, ParseError> {
         let current = self.peek();
+        self.prev = self.position();
         self.advance();
+        self.last_end = self.position();
         current
     }
 
-    pub fn read_exact_char(&mut self, expected: char) -> Result<(bool), String> {
-        let start_pos = self.pos;
-        let current: char;
-        match self.peek() {
-            Ok(c) => current = c,
-            Err(e) => return Err(self.format_eof_error(expected.to_string())),
+    /// Текущая позиция; после `peek` указывает на начало очередной лексемы.
+    pub fn position(&self) -> Position {
+        Position { line: self.line, column: self.pos_in_line, offset: self.byte_pos }
+    }
+
+    /// Позиция сразу за последним считанным через `next` символом.
+    pub fn last_end(&self) -> Position {
+        self.last_end
+    }
+
+    /// Позиция начала последнего считанного через `next` символа.
+    pub fn previous_position(&self) -> Position {
+        self.prev
+    }
+
+    /// Идентификатор: буква, за которой следуют буквы, цифры, `_` или `'`.
+    pub fn read_identifier(&mut self, expected: &str) -> Result<String, ParseError> {
+        let first = match self.peek() {
+            Ok(c) => c,
+            Err(_) => return Err(self.eof_error(expected)),
+        };
+        if !first.is_alphabetic() {
+            return Err(self.unexpected_symbol_error(expected));
+        }
+
+        let start = self.position();
+        let mut identifier = String::new();
+        while let Ok(c) = self.peek_without_skipping() {
+            if !is_identifier_char(c) {
+                break;
+            }
+            self.next()?;
+            identifier.push(c);
+        }
+        self.prev = start;
+        Ok(identifier)
+    }
+
+    /// Лексема из символов, для которых `accept(символ, следующий символ)` истинно.
+    pub fn read_token(&mut self, expected: &str, accept: impl Fn(char, Option<char>) -> bool) -> Result<String, ParseError> {
+        if self.peek().is_err() {
+            return Err(self.eof_error(expected));
         }
+
+        let start = self.position();
+        let mut token = String::new();
+        while let Ok(c) = self.peek_without_skipping() {
+            let following = self.input.get(self.pos as usize + 1).copied();
+            if !accept(c, following) {
+                break;
+            }
+            self.next()?;
+            token.push(c);
+        }
+        if token.is_empty() {
+            return Err(self.unexpected_symbol_error(expected));
+        }
+        self.prev = start;
+        Ok(token)
+    }
+
+    /// Пропускает пробельные символы, включая переводы строк.
+    pub fn skip_whitespace(&mut self) {
+        while let Ok(c) = self.peek() {
+            if c != '\n' && c != '\r' {
+                break;
+            }
+            self.advance();
+        }
+    }
+
+    /// Идентификатор, начинающийся с текущей позиции, без его считывания.
+    pub fn peek_identifier(&mut self) -> Option<String> {
+        let first = self.peek().ok()?;
+        if !first.is_alphabetic() {
+            return None;
+        }
+        Some(self.input[self.pos as usize..]
+            .iter()
+            .take_while(|c| is_identifier_char(**c))
+            .collect())
+    }
+
+    /// Начинается ли остаток входа (после пробелов) с `prefix`.
+    pub fn starts_with(&mut self, prefix: &str) -> bool {
+        self.peek().is_ok() && self.rest_starts_with(prefix)
+    }
+
+    /// Считывает `expected` целиком; последний считанный фрагмент — вся строка.
+    pub fn read_exact_str(&mut self, expected: &str) -> Result<(), ParseError> {
+        self.peek().map_err(|_| self.eof_error(expected))?;
+        let start = self.position();
+        for c in expected.chars() {
+            match self.peek_without_skipping() {
+                Ok(received) if received == c => { self.next()?; }
+                Ok(_) => return Err(self.unexpected_symbol_error(c)),
+                Err(_) => return Err(self.eof_error(c)),
+            }
+        }
+        self.prev = start;
+        Ok(())
+    }
+
+    pub fn read_exact_char(&mut self, expected: char) -> Result<bool, ParseError> {
+        let start_pos = self.pos;
+        let current = match self.peek() {
+            Ok(c) => c,
+            Err(_) => return Err(self.eof_error(expected)),
+        };
         if current == expected {
             self.next()?;
             // Возвращаем true, если были считаны пробельные символы
             Ok(start_pos != self.pos - 1)
         } else {
-            Err(self.format_error(expected.to_string()))
+            Err(self.unexpected_symbol_error(expected))
         }
     }
 
-    pub fn read_eol(&mut self) -> Result<(), String> {
+    pub fn read_eol(&mut self) -> Result<(), ParseError> {
         let current = self.peek();
         match current {
             Ok(_) => {
@@ -180,61 +554,104 @@ impl Parser {
                                     return Ok(());
                                 };
 
-                                Ok({})
+                                Ok(())
                             }
                         }
                     }
-                    _ => Err(self.format_error("eol".parse().unwrap())),
+                    _ => Err(self.unexpected_symbol_error("eol")),
                 }
             }
             Err(_) => Ok(())
         }
     }
 
-    pub fn get_errors(&mut self) -> Vec<String> {
-        return self.errors.clone();
+    /// Пропускает остаток текущей строки вместе с её концом (восстановление после ошибки).
+    pub fn skip_line(&mut self) {
+        while let Ok(current) = self.peek() {
+            if current == '\n' || current == '\r' {
+                break;
+            }
+            self.advance();
+        }
+        let _ = self.read_eol();
+    }
+
+    pub fn get_errors(&mut self) -> Vec<ParseError> {
+        self.errors.clone()
     }
 
-    pub fn add_error(&mut self, message: String) {
-        self.errors.push(message);
+    pub fn has_fatal_errors(&self) -> bool {
+        self.errors.iter().any(|e| e.fatal)
     }
 
-    pub fn format_position(&mut self) -> String {
-        format!("Ошибка в строке {}, на позиции {}. ",
-                self.line, self.pos_in_line)
+    /// Нефатальная ошибка: запоминается, разбор продолжается.
+    pub fn add_error(&mut self, mut error: ParseError) {
+        error.fatal = false;
+        self.errors.push(error);
     }
 
-    pub fn format_previous_position(&mut self) -> String {
-        format!("Ошибка в строке {}, на позиции {}. ",
-                self.prev_line, self.prev_pos_in_line)
+    /// Ошибка, на которой разбор был прерван.
+    pub fn add_fatal_error(&mut self, mut error: ParseError) {
+        error.fatal = true;
+        self.errors.push(error);
     }
 
-    pub fn format_error(&mut self, expected: String) -> String {
-        format!("Ошибка в строке {}, на позиции {}, ожидалось {}, считано '{}'",
-                self.line, self.pos_in_line, expected, self.input[self.pos as usize])
+    /// Пустой фрагмент в текущей позиции.
+    pub fn point_span(&self) -> Span {
+        Span::new(self.position(), self.position())
     }
 
-    pub fn format_eof_error(&mut self, expected: String) -> String {
-        format!("Ошибка в строке {}, на позиции {}, ожидалось: {}, считано EOF",
-                self.line, self.pos_in_line, expected)
+    /// Фрагмент, занимаемый текущим (ещё не считанным) символом.
+    pub fn current_char_span(&self) -> Span {
+        let start = self.position();
+        let end = match self.input.get(self.pos as usize) {
+            Some(c) => Position { line: start.line, column: start.column + 1, offset: start.offset + c.len_utf8() as u32 },
+            None => start,
+        };
+        Span::new(start, end)
     }
 
-    pub fn format_arity_error(&mut self, function: char, expected: String, received: String) -> String {
-        format!("Не совпадает арность функции {}, ожидаемое количество аргументов: {} , считано: {}",
-                function, expected, received)
+    /// Фрагмент от позиции перед последним `next` до конца считанного символа.
+    pub fn previous_span(&self) -> Span {
+        Span::new(self.prev, self.last_end)
     }
 
-    pub fn format_type_error(&mut self, expected: Types, received: Types) -> String {
-        format!("Ошибка в строке {}, на позиции {}, ожидалась {}, считана {}",
-                self.prev_line, self.prev_pos_in_line, expected.as_text(), received.as_text())
+    pub fn error_at(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(kind, self.point_span())
     }
 
-    pub fn format_variables_count_error(&mut self, wrong_variables: HashSet<char>) -> String {
-        let wrong_variables_as_string: String = wrong_variables
-            .iter()
-            .map(|c| c.to_string())
-            .collect::<Vec<String>>()
-            .join(", ");
-        format!("Ошибка в строке {}, следующие переменные входят в правую часть, но не входят в левую: {}", self.line, wrong_variables_as_string)
+    pub fn unexpected_symbol_error(&mut self, expected: impl ToString) -> ParseError {
+        let found = self.input[self.pos as usize];
+        ParseError::new(ParseErrorKind::UnexpectedSymbol, self.current_char_span())
+            .expected(expected)
+            .found(found)
+    }
+
+    pub fn eof_error(&mut self, expected: impl ToString) -> ParseError {
+        ParseError::new(ParseErrorKind::UnexpectedEof, self.point_span())
+            .expected(expected)
     }
-}
\ No newline at end of file
+
+    pub fn arity_error(&mut self, function: &str, span: Span, expected: usize, received: usize) -> ParseError {
+        ParseError::new(ParseErrorKind::ArityMismatch, span)
+            .symbol(function)
+            .expected(expected)
+            .found(received)
+    }
+
+    pub fn type_error(&mut self, expected: Types, received: Types) -> ParseError {
+        ParseError::new(ParseErrorKind::TypeMismatch, self.previous_span())
+            .expected(expected.as_text())
+            .found(received.as_text())
+    }
+
+    pub fn variables_count_error(&mut self, span: Span, wrong_variables: BTreeSet<String>) -> ParseError {
+        let wrong_variables: Vec<String> = wrong_variables.into_iter().collect();
+        ParseError::new(ParseErrorKind::UnboundVariables, span)
+            .symbol(wrong_variables.join(", "))
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '\''
+}
@@ -0,0 +1,323 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::hash_map::Entry;
+use std::fmt;
+use num_traits::{One, Signed, Zero};
+use crate::models::{Coefficient, Polynomial};
+use crate::models::polynomial::{check_limits, power_bound};
+
+// Значения переменных, перебираемые при поиске отрицательного значения
+const NEGATIVE_VALUE_BOUND: i64 = 3;
+const NEGATIVE_VALUE_LIMIT: usize = 4096;
+
+/// Выражение интерпретации над натуральными числами. Сумма, разность, произведение и степень
+/// приводятся к полиному, max и min выносятся наружу, пока это не меняет значения
+/// (`max(a, b) + c = max(a + c, b + c)`, `max(a, b) * c = max(a * c, b * c)` при `c >= 0`);
+/// остальное хранится как сумма или произведение подвыражений.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+pub enum Expression {
+    Polynomial(Polynomial),
+    Max { max: Vec<Expression> },
+    Min { min: Vec<Expression> },
+    Sum { sum: Vec<Expression> },
+    Product { product: Vec<Expression> },
+}
+
+impl Expression {
+    pub fn constant(value: i64) -> Self {
+        Expression::Polynomial(Polynomial::constant(value))
+    }
+
+    pub fn variable(name: &str) -> Self {
+        Expression::Polynomial(Polynomial::variable(name))
+    }
+
+    pub fn as_polynomial(&self) -> Option<&Polynomial> {
+        match self {
+            Expression::Polynomial(polynomial) => Some(polynomial),
+            _ => None,
+        }
+    }
+
+    pub fn max(arguments: Vec<Expression>) -> Self {
+        let mut flat = Vec::new();
+        for argument in arguments {
+            match argument {
+                Expression::Max { max } => flat.extend(max),
+                other => flat.push(other),
+            }
+        }
+        flat.dedup();
+        match flat.len() {
+            1 => flat.pop().unwrap(),
+            _ => Expression::Max { max: flat },
+        }
+    }
+
+    pub fn min(arguments: Vec<Expression>) -> Self {
+        let mut flat = Vec::new();
+        for argument in arguments {
+            match argument {
+                Expression::Min { min } => flat.extend(min),
+                other => flat.push(other),
+            }
+        }
+        flat.dedup();
+        match flat.len() {
+            1 => flat.pop().unwrap(),
+            _ => Expression::Min { min: flat },
+        }
+    }
+
+    pub fn add(&self, other: &Expression) -> Expression {
+        match (self, other) {
+            (Expression::Polynomial(p), Expression::Polynomial(q)) => Expression::Polynomial(p + q),
+            (Expression::Max { max }, _) => Expression::max(max.iter().map(|e| e.add(other)).collect()),
+            (Expression::Min { min }, _) => Expression::min(min.iter().map(|e| e.add(other)).collect()),
+            (_, Expression::Max { .. }) | (_, Expression::Min { .. }) => other.add(self),
+            _ => {
+                let mut sum = Vec::new();
+                for part in [self, other] {
+                    match part {
+                        Expression::Sum { sum: parts } => sum.extend(parts.iter().cloned()),
+                        other => sum.push(other.clone()),
+                    }
+                }
+                // Полиномиальные слагаемые собираются в одно
+                let (polynomials, mut rest): (Vec<Expression>, Vec<Expression>) =
+                    sum.into_iter().partition(|e| e.as_polynomial().is_some());
+                let polynomial = polynomials
+                    .iter()
+                    .fold(Polynomial::zero(), |acc, e| &acc + e.as_polynomial().unwrap());
+                if !polynomial.is_zero() {
+                    rest.push(Expression::Polynomial(polynomial));
+                }
+                match rest.len() {
+                    0 => Expression::constant(0),
+                    1 => rest.pop().unwrap(),
+                    _ => Expression::Sum { sum: rest },
+                }
+            }
+        }
+    }
+
+    pub fn neg(&self) -> Expression {
+        match self {
+            Expression::Polynomial(p) => Expression::Polynomial(-p),
+            Expression::Max { max } => Expression::min(max.iter().map(Expression::neg).collect()),
+            Expression::Min { min } => Expression::max(min.iter().map(Expression::neg).collect()),
+            Expression::Sum { sum } => Expression::Sum { sum: sum.iter().map(Expression::neg).collect() },
+            Expression::Product { product } => {
+                let mut product = product.clone();
+                product.insert(0, Expression::constant(-1));
+                Expression::Product { product }
+            }
+        }
+    }
+
+    pub fn sub(&self, other: &Expression) -> Expression {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Expression) -> Expression {
+        match (self, other) {
+            (Expression::Polynomial(p), Expression::Polynomial(q)) => Expression::Polynomial(p * q),
+            (Expression::Sum { sum }, _) => sum.iter().fold(Expression::constant(0), |acc, e| acc.add(&e.mul(other))),
+            (_, Expression::Sum { .. }) => other.mul(self),
+            (Expression::Max { max }, _) if other.is_nonnegative() =>
+                Expression::max(max.iter().map(|e| e.mul(other)).collect()),
+            (Expression::Min { min }, _) if other.is_nonnegative() =>
+                Expression::min(min.iter().map(|e| e.mul(other)).collect()),
+            (_, Expression::Max { .. }) | (_, Expression::Min { .. }) if self.is_nonnegative() => other.mul(self),
+            _ => {
+                let mut product = Vec::new();
+                for part in [self, other] {
+                    match part {
+                        Expression::Product { product: parts } => product.extend(parts.iter().cloned()),
+                        other => product.push(other.clone()),
+                    }
+                }
+                Expression::Product { product }
+            }
+        }
+    }
+
+    pub fn pow(&self, degree: u32) -> Expression {
+        match self {
+            Expression::Polynomial(p) => Expression::Polynomial(p.pow(degree)),
+            Expression::Max { max } if max.iter().all(Expression::is_nonnegative) =>
+                Expression::max(max.iter().map(|e| e.pow(degree)).collect()),
+            Expression::Min { min } if min.iter().all(Expression::is_nonnegative) =>
+                Expression::min(min.iter().map(|e| e.pow(degree)).collect()),
+            _ => (0..degree).fold(Expression::constant(1), |acc, _| acc.mul(self)),
+        }
+    }
+
+    /// Достаточное условие `e >= 0` при натуральных значениях переменных.
+    pub fn is_nonnegative(&self) -> bool {
+        match self {
+            Expression::Polynomial(p) => p.monomials.iter().all(|m| !m.coefficient.is_negative()),
+            Expression::Max { max } => max.iter().any(Expression::is_nonnegative),
+            Expression::Min { min } => min.iter().all(Expression::is_nonnegative),
+            Expression::Sum { sum } => sum.iter().all(Expression::is_nonnegative),
+            Expression::Product { product } => product.iter().all(Expression::is_nonnegative),
+        }
+    }
+
+    /// Достаточное условие `e > 0` при натуральных значениях переменных.
+    pub fn is_strictly_positive(&self) -> bool {
+        match self {
+            Expression::Polynomial(p) => p.constant_term().is_positive() && p.monomials.iter().all(|m| !m.coefficient.is_negative()),
+            Expression::Max { max } => max.iter().any(Expression::is_strictly_positive),
+            Expression::Min { min } => min.iter().all(Expression::is_strictly_positive),
+            Expression::Sum { sum } =>
+                sum.iter().all(Expression::is_nonnegative) && sum.iter().any(Expression::is_strictly_positive),
+            Expression::Product { product } => product.iter().all(Expression::is_strictly_positive),
+        }
+    }
+
+    /// Подставляет вместо переменных выражения из `substitution`.
+    /// Ошибка — результат превышает `MAX_DEGREE` или `MAX_EXPRESSION_SIZE`.
+    pub fn compose(&self, substitution: &HashMap<String, Expression>) -> Result<Expression, String> {
+        self.compose_with(substitution, &mut HashMap::new())
+    }
+
+    // `powers` — уже вычисленные степени подставляемых выражений
+    fn compose_with<'a>(&'a self, substitution: &HashMap<String, Expression>, powers: &mut HashMap<(&'a String, u32), Expression>) -> Result<Expression, String> {
+        let compose_all = |parts: &'a [Expression], powers: &mut HashMap<(&'a String, u32), Expression>| {
+            parts.iter().map(|e| e.compose_with(substitution, powers)).collect::<Result<Vec<_>, String>>()
+        };
+        let result = match self {
+            Expression::Polynomial(polynomial) => {
+                let mut result = Expression::constant(0);
+                for monomial in &polynomial.monomials {
+                    let mut product = Expression::Polynomial(Polynomial::rational(monomial.coefficient.clone()));
+                    for (variable, degree) in &monomial.variables {
+                        let power = match powers.entry((variable, *degree)) {
+                            Entry::Occupied(entry) => entry.into_mut(),
+                            Entry::Vacant(entry) => {
+                                let base = match substitution.get(variable) {
+                                    Some(expression) => expression.clone(),
+                                    None => Expression::variable(variable),
+                                };
+                                check_limits(power_bound(base.size(), base.variables().len(), base.degree(), *degree), base.degree().saturating_mul(*degree))?;
+                                entry.insert(base.pow(*degree))
+                            }
+                        };
+                        check_limits(product.size().saturating_mul(power.size()), product.degree() + power.degree())?;
+                        product = product.mul(power);
+                    }
+                    result = result.add(&product);
+                    check_limits(result.size(), result.degree())?;
+                }
+                result
+            }
+            Expression::Max { max } => Expression::max(compose_all(max, powers)?),
+            Expression::Min { min } => Expression::min(compose_all(min, powers)?),
+            Expression::Sum { sum } =>
+                compose_all(sum, powers)?.iter().fold(Expression::constant(0), |acc, e| acc.add(e)),
+            Expression::Product { product } => {
+                let mut result = Expression::constant(1);
+                for e in compose_all(product, powers)? {
+                    check_limits(result.size().saturating_mul(e.size()), result.degree() + e.degree())?;
+                    result = result.mul(&e);
+                }
+                result
+            }
+        };
+        check_limits(result.size(), result.degree())?;
+        Ok(result)
+    }
+
+    /// Наибольшая степень монома после раскрытия скобок.
+    pub fn degree(&self) -> u32 {
+        match self {
+            Expression::Polynomial(p) => p.degree(),
+            Expression::Max { max: parts } | Expression::Min { min: parts } | Expression::Sum { sum: parts } =>
+                parts.iter().map(Expression::degree).max().unwrap_or(0),
+            Expression::Product { product } => product.iter().map(Expression::degree).sum(),
+        }
+    }
+
+    /// Число мономов во всех полиномах выражения.
+    pub fn size(&self) -> usize {
+        match self {
+            Expression::Polynomial(p) => p.monomials.len(),
+            Expression::Max { max: parts }
+            | Expression::Min { min: parts }
+            | Expression::Sum { sum: parts }
+            | Expression::Product { product: parts } => parts.iter().map(Expression::size).sum(),
+        }
+    }
+
+    pub fn variables(&self) -> BTreeSet<String> {
+        match self {
+            Expression::Polynomial(p) => p.variables(),
+            Expression::Max { max: parts }
+            | Expression::Min { min: parts }
+            | Expression::Sum { sum: parts }
+            | Expression::Product { product: parts } => parts.iter().flat_map(Expression::variables).collect(),
+        }
+    }
+
+    /// Точное значение выражения; `None`, если какой-то переменной нет в `values`.
+    pub fn evaluate(&self, values: &HashMap<String, i64>) -> Option<Coefficient> {
+        match self {
+            Expression::Polynomial(p) => p.evaluate_exact(values),
+            Expression::Max { max } => max.iter().map(|e| e.evaluate(values)).collect::<Option<Vec<Coefficient>>>()?.into_iter().max(),
+            Expression::Min { min } => min.iter().map(|e| e.evaluate(values)).collect::<Option<Vec<Coefficient>>>()?.into_iter().min(),
+            Expression::Sum { sum } => sum.iter().try_fold(Coefficient::zero(), |acc, e| Some(acc + e.evaluate(values)?)),
+            Expression::Product { product } => product.iter().try_fold(Coefficient::one(), |acc, e| Some(acc * e.evaluate(values)?)),
+        }
+    }
+
+    /// Набор натуральных значений переменных, при котором выражение отрицательно,
+    /// вместе с этим значением (перебираются значения от 0 до 3).
+    pub fn find_negative_value(&self) -> Option<(BTreeMap<String, i64>, Coefficient)> {
+        if self.is_nonnegative() {
+            return None;
+        }
+        let variables: Vec<String> = self.variables().into_iter().collect();
+        let mut assignment: HashMap<String, i64> = variables.iter().map(|v| (v.clone(), 0)).collect();
+
+        for _ in 0..NEGATIVE_VALUE_LIMIT {
+            if let Some(value) = self.evaluate(&assignment) {
+                if value.is_negative() {
+                    return Some((assignment.into_iter().collect(), value));
+                }
+            }
+
+            // Следующий набор значений в порядке счётчика с основанием NEGATIVE_VALUE_BOUND + 1
+            let mut carried = true;
+            for variable in &variables {
+                let value = assignment.get_mut(variable).unwrap();
+                if *value < NEGATIVE_VALUE_BOUND {
+                    *value += 1;
+                    carried = false;
+                    break;
+                }
+                *value = 0;
+            }
+            if carried {
+                break;
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |parts: &[Expression], separator: &str| {
+            parts.iter().map(|e| e.to_string()).collect::<Vec<String>>().join(separator)
+        };
+        match self {
+            Expression::Polynomial(p) => write!(f, "{}", p),
+            Expression::Max { max } => write!(f, "max({})", join(max, ", ")),
+            Expression::Min { min } => write!(f, "min({})", join(min, ", ")),
+            Expression::Sum { sum } => write!(f, "({})", join(sum, " + ")),
+            Expression::Product { product } => write!(f, "({})", join(product, " * ")),
+        }
+    }
+}
@@ -1,3 +1,11 @@
 pub mod data_structures;
+pub mod parse_error;
+pub mod polynomial;
+pub mod expression;
+pub mod arctic;
 
-pub use data_structures::{Parser, ParsedData, ParsedDataTRS, ParsedDataInterpret};
\ No newline at end of file
+pub use data_structures::{Parser, ParsedData, ParsedDataTRS, ParsedDataInterpret, ParsedDataMatrix, ParsedDataArctic};
+pub use parse_error::{ParseError, ParseErrorKind};
+pub use polynomial::{Coefficient, Monomial, Polynomial};
+pub use expression::Expression;
+pub use arctic::{ArcticMonomial, ArcticPolynomial};
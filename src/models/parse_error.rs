@@ -0,0 +1,213 @@
+use std::fmt;
+use serde::ser::SerializeStruct;
+use crate::models::data_structures::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParseErrorKind {
+    UnexpectedSymbol,
+    UnexpectedEof,
+    TypeMismatch,
+    ArityMismatch,
+    UnboundVariables,
+    VariableLeftSide,
+    DuplicateRule,
+    IdenticalSides,
+    MissingVariablesEquals,
+    DuplicateVariable,
+    NoVariables,
+    NoRules,
+    UnknownSymbol,
+    UndeclaredFunction,
+    UndeclaredConstant,
+    MissingFunctionInterpretation,
+    MissingConstantInterpretation,
+    InterpretationArityMismatch,
+    DuplicateFunctionInterpretation,
+    DuplicateConstantInterpretation,
+    DuplicateArgument,
+    InvalidVariableName,
+    UnknownVariable,
+    ZeroCoefficient,
+    NumberTooLarge,
+    DegreeTooLarge,
+    ExpressionTooLarge,
+    UnknownSection,
+    PrecedenceCycle,
+    DuplicateWeight,
+    MatrixDimensionMismatch,
+    NegativeInterpretation,
+    InvalidDenominator,
+    NonMonotoneInterpretation,
+    ValueBelowMinimum,
+    ConstantBelowMinimum,
+    UnterminatedComment,
+}
+
+/// Ошибка разбора. Фатальная ошибка прерывает разбор, нефатальные накапливаются
+/// в `Parser` и возвращаются вместе с ней.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+    pub expected: Option<String>,
+    pub found: Option<String>,
+    pub symbol: Option<String>,
+    /// Строка, на которую ссылается ошибка (например, первое вхождение повторяющегося правила).
+    pub related_line: Option<u32>,
+    pub fatal: bool,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, span: Span) -> Self {
+        ParseError {
+            kind,
+            span,
+            expected: None,
+            found: None,
+            symbol: None,
+            related_line: None,
+            fatal: true,
+        }
+    }
+
+    pub fn expected(mut self, expected: impl ToString) -> Self {
+        self.expected = Some(expected.to_string());
+        self
+    }
+
+    pub fn found(mut self, found: impl ToString) -> Self {
+        self.found = Some(found.to_string());
+        self
+    }
+
+    pub fn symbol(mut self, symbol: impl ToString) -> Self {
+        self.symbol = Some(symbol.to_string());
+        self
+    }
+
+    pub fn related_line(mut self, line: u32) -> Self {
+        self.related_line = Some(line);
+        self
+    }
+
+    /// Предупреждение, которое не мешает дальнейшей обработке.
+    pub fn non_fatal(mut self) -> Self {
+        self.fatal = false;
+        self
+    }
+
+    /// Текст ошибки для пользователя.
+    pub fn message(&self) -> String {
+        let line = self.span.start.line;
+        let column = self.span.start.column;
+        let pos = format!("Ошибка в строке {}, на позиции {}. ", line, column);
+        let expected = self.expected.as_deref().unwrap_or_default();
+        let found = self.found.as_deref().unwrap_or_default();
+        let symbol = self.symbol.as_deref().unwrap_or_default();
+
+        match self.kind {
+            ParseErrorKind::UnexpectedSymbol =>
+                format!("Ошибка в строке {}, на позиции {}, ожидалось {}, считано '{}'", line, column, expected, found),
+            ParseErrorKind::UnexpectedEof => match &self.expected {
+                Some(expected) => format!("Ошибка в строке {}, на позиции {}, ожидалось: {}, считано EOF", line, column, expected),
+                None => format!("Ошибка в строке {}, на позиции {}, неожиданный конец ввода", line, column),
+            },
+            ParseErrorKind::TypeMismatch =>
+                format!("Ошибка в строке {}, на позиции {}, ожидалась {}, считана {}", line, column, expected, found),
+            ParseErrorKind::ArityMismatch =>
+                format!("Не совпадает арность функции {}, ожидаемое количество аргументов: {} , считано: {}", symbol, expected, found),
+            ParseErrorKind::UnboundVariables =>
+                format!("Ошибка в строке {}, следующие переменные входят в правую часть, но не входят в левую: {}", line, symbol),
+            ParseErrorKind::VariableLeftSide =>
+                format!("{}Левая часть правила не может быть переменной {}", pos, symbol),
+            ParseErrorKind::DuplicateRule =>
+                format!("{}Правило {} повторяет правило в строке {}", pos, symbol, self.related_line.unwrap_or_default()),
+            ParseErrorKind::IdenticalSides =>
+                format!("{}Левая и правая части правила {} совпадают", pos, symbol),
+            ParseErrorKind::MissingVariablesEquals =>
+                format!("{}Не хватает '=' в списке переменных", pos),
+            ParseErrorKind::DuplicateVariable =>
+                format!("Переменная {} объявлена несколько раз", symbol),
+            ParseErrorKind::NoVariables =>
+                format!("{}У функции не найдено ни одной переменной", pos),
+            ParseErrorKind::NoRules =>
+                "Правила переписывания не обнаружены".to_string(),
+            ParseErrorKind::UnknownSymbol =>
+                format!("{}Ожидалась функция или константа, считано {}", pos, found),
+            ParseErrorKind::UndeclaredFunction =>
+                format!("{}Функция {} не объявлена в TRS", pos, symbol),
+            ParseErrorKind::UndeclaredConstant =>
+                format!("{}Константы {} нет в TRS, но она присутствует в интерпретации", pos, symbol),
+            ParseErrorKind::MissingFunctionInterpretation =>
+                format!("Функция {} была объявлена в TRS, но её нет в интерпретации", symbol),
+            ParseErrorKind::MissingConstantInterpretation =>
+                format!("Константа {} была объявлена в TRS, но её нет в интерпретации", symbol),
+            ParseErrorKind::InterpretationArityMismatch =>
+                format!("{}Количество переменных в интерпретации функции {} не совпадает с количеством переменных в TRS", pos, symbol),
+            ParseErrorKind::DuplicateFunctionInterpretation =>
+                format!("{}Интерпретация функции {} уже была определена", pos, symbol),
+            ParseErrorKind::DuplicateConstantInterpretation =>
+                format!("{}Интерпретация константы {} уже была определена", pos, symbol),
+            ParseErrorKind::DuplicateArgument =>
+                format!("{}Переменная {} уже была указана в числе аргументов данной функции", pos, symbol),
+            ParseErrorKind::InvalidVariableName =>
+                format!("{}Ожидался символ алфавита (буква) в названии переменной, считано: {}", pos, found),
+            ParseErrorKind::UnknownVariable =>
+                format!("{}Переменная {} не указана в качестве аргумента функции", pos, symbol),
+            ParseErrorKind::ZeroCoefficient =>
+                format!("{}Коэффициент не может быть равен 0", pos),
+            ParseErrorKind::NumberTooLarge =>
+                format!("{}Число {} слишком велико", pos, found),
+            ParseErrorKind::DegreeTooLarge =>
+                format!("{}Степень {} больше допустимой {}", pos, found, expected),
+            ParseErrorKind::ExpressionTooLarge =>
+                format!("{}После раскрытия скобок выражение содержит больше {} мономов", pos, expected),
+            ParseErrorKind::UnknownSection =>
+                format!("{}Неизвестный раздел {}, ожидалось {}", pos, found, expected),
+            ParseErrorKind::PrecedenceCycle =>
+                format!("{}Отношение {} противоречит заданному ранее порядку", pos, symbol),
+            ParseErrorKind::DuplicateWeight =>
+                format!("{}Вес символа {} уже был задан", pos, symbol),
+            ParseErrorKind::MatrixDimensionMismatch =>
+                format!("{}Размерность {} не совпадает с размерностью интерпретации {}", pos, found, expected),
+            ParseErrorKind::NegativeInterpretation =>
+                format!("{}Интерпретация символа {} принимает отрицательное значение {} при {}", pos, symbol, found, expected),
+            ParseErrorKind::InvalidDenominator =>
+                format!("{}Знаменатель дроби должен быть положительным, считано {}", pos, found),
+            ParseErrorKind::NonMonotoneInterpretation =>
+                format!("{}Не удалось доказать строгую монотонность интерпретации функции {} по аргументу {}", pos, symbol, found),
+            ParseErrorKind::ValueBelowMinimum =>
+                format!("{}Интерпретация функции {} при аргументах, равных {}, принимает значение {}, меньшее {}", pos, symbol, expected, found, expected),
+            ParseErrorKind::ConstantBelowMinimum =>
+                format!("{}Значение константы {} равно {}, а должно быть не меньше {}", pos, symbol, found, expected),
+            ParseErrorKind::UnterminatedComment =>
+                format!("{}Комментарий /* не закрыт", pos),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl serde::Serialize for ParseError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ParseError", 7 + self.related_line.is_some() as usize)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("message", &self.message())?;
+        state.serialize_field("span", &self.span)?;
+        state.serialize_field("expected", &self.expected)?;
+        state.serialize_field("found", &self.found)?;
+        state.serialize_field("symbol", &self.symbol)?;
+        match &self.related_line {
+            Some(line) => state.serialize_field("related_line", line)?,
+            None => state.skip_field("related_line")?,
+        }
+        state.serialize_field("fatal", &self.fatal)?;
+        state.end()
+    }
+}
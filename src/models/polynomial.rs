@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::hash_map::Entry;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+/// Точный рациональный коэффициент произвольной величины.
+pub type Coefficient = BigRational;
+
+// Ограничения на раскрытие скобок при разборе интерпретации и подстановке в неё:
+// запись вроде `(x + y){100000}` или `f(f(f(x)))` при `f(x) = (x + 1){100}` не раскрывается целиком
+pub const MAX_DEGREE: u32 = 100;
+pub const MAX_EXPRESSION_SIZE: usize = 1000;
+
+pub fn integer(value: i64) -> Coefficient {
+    Coefficient::from_integer(BigInt::from(value))
+}
+
+/// Целые коэффициенты, помещающиеся в `i64`, записываются числом, остальные — строкой
+/// (`"3/2"`, `"123456789012345678901"`).
+pub(crate) fn serialize_coefficient<S: serde::Serializer>(value: &Coefficient, serializer: S) -> Result<S::Ok, S::Error> {
+    match value.is_integer().then(|| value.to_integer().to_i64()).flatten() {
+        Some(value) => serializer.serialize_i64(value),
+        None => serializer.collect_str(value),
+    }
+}
+
+/// Моном `coefficient * x1^d1 * ... * xn^dn`, степени хранятся по имени переменной.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+pub struct Monomial {
+    #[serde(serialize_with = "serialize_coefficient")]
+    pub coefficient: Coefficient,
+    pub variables: BTreeMap<String, u32>,
+}
+
+/// Полином в нормальной форме: подобные мономы приведены, нулевые отброшены,
+/// мономы упорядочены по набору переменных.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(serde::Serialize)]
+pub struct Polynomial {
+    pub monomials: Vec<Monomial>,
+}
+
+impl Monomial {
+    pub fn new(coefficient: Coefficient, variables: BTreeMap<String, u32>) -> Self {
+        Monomial { coefficient, variables }
+    }
+
+    pub fn constant(coefficient: Coefficient) -> Self {
+        Monomial { coefficient, variables: BTreeMap::new() }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    pub fn degree(&self) -> u32 {
+        self.variables.values().sum()
+    }
+
+    /// Домножает моном на `variable^degree`.
+    pub fn multiply_variable(&mut self, variable: &str, degree: u32) {
+        *self.variables.entry(variable.to_string()).or_insert(0) += degree;
+    }
+
+    pub fn evaluate(&self, values: &HashMap<String, i64>) -> Option<Coefficient> {
+        self.variables.iter().try_fold(self.coefficient.clone(), |acc, (variable, degree)| {
+            let value = num_traits::pow(BigInt::from(*values.get(variable)?), *degree as usize);
+            Some(acc * Coefficient::from_integer(value))
+        })
+    }
+}
+
+impl Mul for &Monomial {
+    type Output = Monomial;
+
+    fn mul(self, other: &Monomial) -> Monomial {
+        let mut result = Monomial::new(&self.coefficient * &other.coefficient, self.variables.clone());
+        for (variable, degree) in &other.variables {
+            result.multiply_variable(variable, *degree);
+        }
+        result
+    }
+}
+
+impl Polynomial {
+    pub fn zero() -> Self {
+        Polynomial { monomials: Vec::new() }
+    }
+
+    pub fn constant(value: i64) -> Self {
+        Polynomial::rational(integer(value))
+    }
+
+    pub fn rational(value: Coefficient) -> Self {
+        Polynomial::from_monomials(vec![Monomial::constant(value)])
+    }
+
+    pub fn variable(name: &str) -> Self {
+        let mut monomial = Monomial::constant(Coefficient::one());
+        monomial.multiply_variable(name, 1);
+        Polynomial::from_monomials(vec![monomial])
+    }
+
+    pub fn from_monomials(monomials: Vec<Monomial>) -> Self {
+        let mut collected: BTreeMap<BTreeMap<String, u32>, Coefficient> = BTreeMap::new();
+        for monomial in monomials {
+            *collected.entry(monomial.variables).or_insert_with(Coefficient::zero) += monomial.coefficient;
+        }
+        Polynomial {
+            monomials: collected
+                .into_iter()
+                .filter(|(_, coefficient)| !coefficient.is_zero())
+                .map(|(variables, coefficient)| Monomial::new(coefficient, variables))
+                .collect(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.monomials.is_empty()
+    }
+
+    pub fn variables(&self) -> BTreeSet<String> {
+        self.monomials
+            .iter()
+            .flat_map(|m| m.variables.keys().cloned())
+            .collect()
+    }
+
+    /// Свободный член полинома.
+    pub fn constant_term(&self) -> Coefficient {
+        self.coefficient(&BTreeMap::new())
+    }
+
+    /// Коэффициент при мономе с заданным набором переменных (0, если такого монома нет).
+    pub fn coefficient(&self, variables: &BTreeMap<String, u32>) -> Coefficient {
+        self.monomials
+            .iter()
+            .find(|m| &m.variables == variables)
+            .map_or_else(Coefficient::zero, |m| m.coefficient.clone())
+    }
+
+    /// Наибольшая степень монома (0 у нулевого полинома).
+    pub fn degree(&self) -> u32 {
+        self.monomials.iter().map(Monomial::degree).max().unwrap_or(0)
+    }
+
+    pub fn pow(&self, degree: u32) -> Polynomial {
+        (0..degree).fold(Polynomial::constant(1), |acc, _| &acc * self)
+    }
+
+    /// Подставляет вместо переменных полиномы из `substitution`;
+    /// переменные, для которых подстановки нет, остаются без изменений.
+    /// Ошибка — результат превышает `MAX_DEGREE` или `MAX_EXPRESSION_SIZE`.
+    pub fn compose(&self, substitution: &HashMap<String, Polynomial>) -> Result<Polynomial, String> {
+        let mut powers: HashMap<(&String, u32), Polynomial> = HashMap::new();
+        let mut result = Polynomial::zero();
+        for monomial in &self.monomials {
+            let mut product = Polynomial::rational(monomial.coefficient.clone());
+            for (variable, degree) in &monomial.variables {
+                let power = match powers.entry((variable, *degree)) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => {
+                        let base = match substitution.get(variable) {
+                            Some(polynomial) => polynomial.clone(),
+                            None => Polynomial::variable(variable),
+                        };
+                        check_limits(power_bound(base.monomials.len(), base.variables().len(), base.degree(), *degree), base.degree().saturating_mul(*degree))?;
+                        entry.insert(base.pow(*degree))
+                    }
+                };
+                check_limits(product.monomials.len().saturating_mul(power.monomials.len()), product.degree() + power.degree())?;
+                product = &product * power;
+            }
+            result = &result + &product;
+            check_limits(result.monomials.len(), result.degree())?;
+        }
+        Ok(result)
+    }
+
+    /// Значение полинома при заданных значениях переменных;
+    /// `None`, если какой-то переменной нет в `values` или значение не целое либо не помещается в `i64`.
+    pub fn evaluate(&self, values: &HashMap<String, i64>) -> Option<i64> {
+        let value = self.evaluate_exact(values)?;
+        value.is_integer().then(|| value.to_integer().to_i64()).flatten()
+    }
+
+    /// Точное значение полинома; `None`, если какой-то переменной нет в `values`.
+    pub fn evaluate_exact(&self, values: &HashMap<String, i64>) -> Option<Coefficient> {
+        self.monomials
+            .iter()
+            .try_fold(Coefficient::zero(), |acc, monomial| Some(acc + monomial.evaluate(values)?))
+    }
+}
+
+/// Верхняя оценка числа мономов в `p^degree`, если в `p` их `size`: C(size + degree - 1, degree).
+/// Счёт прекращается, как только оценка превысила `MAX_EXPRESSION_SIZE`.
+pub(crate) fn power_size(size: usize, degree: u32) -> usize {
+    let mut result: usize = 1;
+    for i in 1..=degree as usize {
+        result = result * (size + i - 1) / i;
+        if result > MAX_EXPRESSION_SIZE {
+            break;
+        }
+    }
+    result
+}
+
+/// Оценка числа мономов в `p^degree`: не больше `power_size` и не больше числа мономов
+/// от `variables` переменных степени не выше `base_degree * degree`.
+pub(crate) fn power_bound(size: usize, variables: usize, base_degree: u32, degree: u32) -> usize {
+    power_size(size, degree).min(power_size(variables + 1, base_degree.saturating_mul(degree)))
+}
+
+/// Ошибка, если выражение с `size` мономами степени `degree` превышает ограничения.
+pub(crate) fn check_limits(size: usize, degree: u32) -> Result<(), String> {
+    if degree > MAX_DEGREE {
+        return Err(format!("Степень {} больше допустимой {}", degree, MAX_DEGREE));
+    }
+    if size > MAX_EXPRESSION_SIZE {
+        return Err(format!("После раскрытия скобок выражение содержит больше {} мономов", MAX_EXPRESSION_SIZE));
+    }
+    Ok(())
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        Polynomial::from_monomials(self.monomials.iter().chain(&other.monomials).cloned().collect())
+    }
+}
+
+impl Neg for &Polynomial {
+    type Output = Polynomial;
+
+    fn neg(self) -> Polynomial {
+        Polynomial {
+            monomials: self.monomials
+                .iter()
+                .map(|m| Monomial::new(-&m.coefficient, m.variables.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl Sub for &Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, other: &Polynomial) -> Polynomial {
+        self + &-other
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        let mut monomials = Vec::new();
+        for left in &self.monomials {
+            for right in &other.monomials {
+                monomials.push(left * right);
+            }
+        }
+        Polynomial::from_monomials(monomials)
+    }
+}
+
+impl fmt::Display for Monomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.coefficient.is_one() || self.variables.is_empty() {
+            parts.push(self.coefficient.to_string());
+        }
+        for (variable, degree) in &self.variables {
+            if *degree == 1 {
+                parts.push(variable.clone());
+            } else {
+                parts.push(format!("{}^{}", variable, degree));
+            }
+        }
+        write!(f, "{}", parts.join(" * "))
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.monomials.is_empty() {
+            return write!(f, "(0)");
+        }
+        let mut rendered = String::new();
+        for (i, monomial) in self.monomials.iter().enumerate() {
+            let absolute = Monomial::new(monomial.coefficient.abs(), monomial.variables.clone());
+            match (i, monomial.coefficient.is_negative()) {
+                (0, false) => rendered.push_str(&absolute.to_string()),
+                (0, true) => rendered.push_str(&format!("-{}", absolute)),
+                (_, false) => rendered.push_str(&format!(" + {}", absolute)),
+                (_, true) => rendered.push_str(&format!(" - {}", absolute)),
+            }
+        }
+        write!(f, "({})", rendered)
+    }
+}
@@ -1,11 +1,15 @@
 pub mod parser_trs;
 pub mod parser_interpret;
+pub mod parser_matrix;
+pub mod parser_arctic;
 
-pub use parser_trs::ParserTRS;
+pub use parser_trs::{ParserTRS, TrsFormat};
 pub use parser_interpret::ParserInterpret;
+pub use parser_matrix::ParserMatrix;
+pub use parser_arctic::ParserArctic;
 
-use crate::models::ParsedData;
+use crate::models::{ParseError, ParsedData};
 
 pub trait Parse {
-    fn parse(&mut self) -> Result<(ParsedData), Vec<String>>;
+    fn parse(&mut self) -> Result<ParsedData, Vec<ParseError>>;
 }
\ No newline at end of file
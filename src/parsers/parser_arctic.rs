@@ -0,0 +1,240 @@
+use std::collections::HashSet;
+use super::Parse;
+use crate::models::{ArcticPolynomial, ParseError, ParseErrorKind, ParsedData, Parser};
+use crate::models::data_structures::{ArcticInterpretFunction, Model, Span, Types};
+
+/// Разбор арктической интерпретации:
+/// ```text
+/// f(x, y) = max(x + 1, y, -inf)
+/// a = 0
+/// ```
+/// `+` — арктическое умножение (обычное сложение), `max(...)` — арктическое сложение,
+/// `-inf` — ноль полукольца. Допускаются целые числа со знаком и скобки.
+#[derive(Debug)]
+pub struct ParserArctic {
+    parser: Parser,
+    model_from_trs: Model,
+    own_functions: HashSet<String>,
+    own_constants: HashSet<String>,
+}
+
+impl ParserArctic {
+    pub fn new(input: &str, model: Model) -> Self {
+        ParserArctic {
+            parser: Parser::new(input),
+            model_from_trs: model,
+            own_functions: HashSet::new(),
+            own_constants: HashSet::new(),
+        }
+    }
+}
+
+impl Parse for ParserArctic {
+    fn parse(&mut self) -> Result<ParsedData, Vec<ParseError>> {
+        let mut result = Vec::new();
+
+        loop {
+            self.parser.skip_whitespace();
+            match self.parse_symbol() {
+                Ok(function) => result.push(function),
+                Err(e) => {
+                    self.parser.add_fatal_error(e);
+                    return Err(self.parser.get_errors());
+                }
+            }
+
+            if let Err(e) = self.parser.read_eol() {
+                self.parser.add_fatal_error(e);
+                return Err(self.parser.get_errors());
+            }
+            self.parser.skip_whitespace();
+            if self.parser.peek().is_err() {
+                break;
+            }
+        }
+
+        let mut functions: Vec<&String> = self.model_from_trs.functions.keys().collect();
+        functions.sort();
+        for k in functions {
+            if !self.own_functions.contains(k) {
+                let e = self.parser.error_at(ParseErrorKind::MissingFunctionInterpretation).symbol(k);
+                self.parser.add_error(e);
+            }
+        } //non fatal
+
+        let mut constants: Vec<&String> = self.model_from_trs.constants.iter().collect();
+        constants.sort();
+        for v in constants {
+            if !self.own_constants.contains(v) {
+                let e = self.parser.error_at(ParseErrorKind::MissingConstantInterpretation).symbol(v);
+                self.parser.add_error(e);
+            }
+        } //non fatal
+
+        if !self.parser.get_errors().is_empty() {
+            return Err(self.parser.get_errors());
+        }
+        Ok(ParsedData::Arctic(result))
+    }
+}
+
+impl ParserArctic {
+    fn parse_symbol(&mut self) -> Result<ArcticInterpretFunction, ParseError> {
+        self.parser.peek().map_err(|_| self.parser.eof_error("функция или константа"))?;
+        let start = self.parser.position();
+        let name = self.parser.read_identifier("функция или константа")?;
+        let name_span = Span::new(start, self.parser.last_end());
+
+        let arity = if let Some(arity) = self.model_from_trs.functions.get(&name) {
+            *arity
+        } else if self.model_from_trs.constants.contains(&name) {
+            0
+        } else {
+            return Err(ParseError::new(ParseErrorKind::UnknownSymbol, name_span).found(name));
+        };
+
+        let variables = match self.parser.peek() {
+            Ok('(') => {
+                self.parser.next()?;
+                self.parse_arguments()?
+            }
+            _ => Vec::new(),
+        };
+        if variables.len() as i32 != arity {
+            let e = ParseError::new(ParseErrorKind::InterpretationArityMismatch, Span::new(start, self.parser.last_end()))
+                .symbol(&name)
+                .expected(arity)
+                .found(variables.len());
+            self.parser.add_error(e);
+        } // non fatal
+
+        self.parser.read_exact_char('=')?;
+        let polynomial = self.parse_sum(&variables)?;
+        match self.parser.peek() {
+            Ok('\n') | Ok('\r') | Err(_) => (),
+            Ok(_) => return Err(self.parser.unexpected_symbol_error("'+' или конец строки")),
+        }
+
+        let (own, kind) = match arity {
+            0 => (&mut self.own_constants, ParseErrorKind::DuplicateConstantInterpretation),
+            _ => (&mut self.own_functions, ParseErrorKind::DuplicateFunctionInterpretation),
+        };
+        if !own.insert(name.clone()) {
+            self.parser.add_error(ParseError::new(kind, name_span).symbol(&name));
+        } // non fatal
+
+        Ok(ArcticInterpretFunction {
+            name,
+            variables,
+            expression: polynomial.to_string(),
+            polynomial,
+            span: Span::new(start, self.parser.last_end()),
+        })
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut variables = Vec::new();
+        loop {
+            let current = self.parser.read_identifier("переменная")?;
+            if self.model_from_trs.functions.contains_key(&current) {
+                let e = self.parser.type_error(Types::VARIABLE, Types::FUNCTION);
+                self.parser.add_error(e);
+            } else if self.model_from_trs.constants.contains(&current) {
+                let e = self.parser.type_error(Types::VARIABLE, Types::CONSTANT);
+                self.parser.add_error(e);
+            } //non fatal
+
+            if variables.contains(&current) {
+                let e = ParseError::new(ParseErrorKind::DuplicateArgument, self.parser.previous_span()).symbol(&current);
+                self.parser.add_error(e);
+            } //non fatal
+            variables.push(current);
+
+            match self.parser.peek() {
+                Ok(')') => {
+                    self.parser.next()?;
+                    return Ok(variables);
+                }
+                Ok(',') => { self.parser.next()?; }
+                Ok(_) => return Err(self.parser.unexpected_symbol_error("',' или ')'")),
+                Err(_) => return Err(self.parser.eof_error("')' или ','")),
+            }
+        }
+    }
+
+    /// Арктическое произведение `a1 + ... + an`.
+    fn parse_sum(&mut self, variables: &[String]) -> Result<ArcticPolynomial, ParseError> {
+        self.parser.peek().map_err(|_| self.parser.eof_error("число, -inf, max(...) или переменная"))?;
+        let start = self.parser.position();
+        let mut result = self.parse_atom(variables)?;
+        while let Ok('+') = self.parser.peek() {
+            self.parser.next()?;
+            let atom = self.parse_atom(variables)?;
+            result = match result.plus(&atom) {
+                Some(sum) => sum,
+                None => {
+                    let span = Span::new(start, self.parser.last_end());
+                    return Err(ParseError::new(ParseErrorKind::NumberTooLarge, span).found(format!("{} + {}", result, atom)));
+                }
+            };
+        }
+        Ok(result)
+    }
+
+    fn parse_atom(&mut self, variables: &[String]) -> Result<ArcticPolynomial, ParseError> {
+        match self.parser.peek() {
+            Err(_) => Err(self.parser.eof_error("число, -inf, max(...) или переменная")),
+            Ok('(') => {
+                self.parser.next()?;
+                let result = self.parse_sum(variables)?;
+                self.parser.read_exact_char(')')?;
+                Ok(result)
+            }
+            Ok('-') => {
+                self.parser.next()?;
+                if let Ok('i') = self.parser.peek_without_skipping() {
+                    self.parser.read_exact_str("inf")?;
+                    return Ok(ArcticPolynomial::neg_infinity());
+                }
+                Ok(ArcticPolynomial::constant(-self.parse_number()?))
+            }
+            Ok(c) if c.is_ascii_digit() => Ok(ArcticPolynomial::constant(self.parse_number()?)),
+            Ok(c) if c.is_alphabetic() => {
+                let name = self.parser.read_identifier("переменная")?;
+                if name == "max" && matches!(self.parser.peek(), Ok('(')) {
+                    return self.parse_max(variables);
+                }
+                if !variables.contains(&name) {
+                    return Err(ParseError::new(ParseErrorKind::UnknownVariable, self.parser.previous_span()).symbol(name));
+                }
+                Ok(ArcticPolynomial::variable(&name))
+            }
+            Ok(_) => Err(self.parser.unexpected_symbol_error("число, -inf, max(...) или переменная")),
+        }
+    }
+
+    // Имя max уже считано
+    fn parse_max(&mut self, variables: &[String]) -> Result<ArcticPolynomial, ParseError> {
+        self.parser.read_exact_char('(')?;
+        let mut result = ArcticPolynomial::neg_infinity();
+        loop {
+            result = result.max(&self.parse_sum(variables)?);
+            match self.parser.peek() {
+                Ok(',') => { self.parser.next()?; }
+                Ok(')') => {
+                    self.parser.next()?;
+                    return Ok(result);
+                }
+                Ok(_) => return Err(self.parser.unexpected_symbol_error("',' или ')'")),
+                Err(_) => return Err(self.parser.eof_error("')'")),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<i64, ParseError> {
+        let number = self.parser.read_token("число", |c, _| c.is_ascii_digit())?;
+        number
+            .parse::<i64>()
+            .map_err(|_| ParseError::new(ParseErrorKind::NumberTooLarge, self.parser.previous_span()).found(&number))
+    }
+}
@@ -1,15 +1,21 @@
 use std::string::String;
 use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
 use super::Parse;
-use crate::models::{ParsedData, Parser};
-use crate::models::data_structures::{Model, ParsedInterpretFunction, Types};
+use std::str::FromStr;
+use num_bigint::BigInt;
+use num_traits::Zero;
+use crate::models::{Coefficient, Expression, ParseError, ParseErrorKind, ParsedData, Parser, Polynomial};
+use crate::models::data_structures::{Model, ParsedInterpretFunction, Span, Types};
+use crate::models::polynomial::{power_size, MAX_DEGREE, MAX_EXPRESSION_SIZE};
 
 #[derive(Debug)]
 pub struct ParserInterpret {
     parser: Parser,
     model_from_trs: Model,
-    own_functions: HashMap<char,i32>,
-    own_constants: HashSet<char>,
+    own_functions: HashMap<String,i32>,
+    own_constants: HashSet<String>,
+    partial: bool,
 }
 
 impl ParserInterpret {
@@ -19,56 +25,54 @@ impl ParserInterpret {
             model_from_trs: model,
             own_functions: HashMap::new(),
             own_constants: HashSet::new(),
-
+            partial: false,
         }
     }
+
+    /// Разбор интерпретации, в которой заданы не все символы TRS
+    /// (например, остальные ищутся решателем).
+    pub fn partial(input: &str, model: Model) -> Self {
+        ParserInterpret { partial: true, ..ParserInterpret::new(input, model) }
+    }
 }
 
 impl Parse for ParserInterpret {
-    fn parse(&mut self) -> Result<(ParsedData), Vec<String>> {
+    fn parse(&mut self) -> Result<ParsedData, Vec<ParseError>> {
         let mut result = Vec::new();
+        // Символы, на строках которых разбор прервался
+        let mut failed = HashSet::new();
 
         loop {
-            let res1 = match self.parse_function_or_const(){
-                Ok(r) => r,
+            self.parser.skip_whitespace();
+            let line = self.parser.position().line;
+            let symbol = self.parser.peek_identifier();
+            match self.parse_line() {
+                Ok(function) => result.push(function),
+                // Ошибка запоминается, разбор продолжается со следующей строки
                 Err(e) => {
-                    self.parser.add_error(e);
-                    return Err(self.parser.get_errors());
-                }
-            };
-            result.push(res1);
-
-            match self.parser.peek() {
-                Err(_) => break,
-                Ok(_) => {
-                    match self.parser.read_eol(){
-                        Ok(_) => (),
-                        Err(e) => {
-                            self.parser.add_error(e);
-                            return Err(self.parser.get_errors());
-                        }
-                    }
-
-                    match self.parser.peek() {
-                        Err(_) => break,
-                        Ok(_) => {}
+                    self.parser.add_fatal_error(e);
+                    failed.extend(symbol);
+                    if self.parser.position().line == line {
+                        self.parser.skip_line();
                     }
                 }
             }
+
+            self.parser.skip_whitespace();
+            if self.parser.peek().is_err() { break }
         }
 
-        for (k, v) in &self.model_from_trs.functions {
-            match self.own_functions.get(k) {
-                None => {
-                    self.parser.add_error(format!("Функция {} была объявлена в TRS, но её нет в интерпретации", k));
-                }
-                Some(_) => ()
+        for k in self.model_from_trs.functions.keys() {
+            if !self.partial && !self.own_functions.contains_key(k) && !failed.contains(k) {
+                let e = self.parser.error_at(ParseErrorKind::MissingFunctionInterpretation).symbol(k);
+                self.parser.add_error(e);
             }
         } //non fatal
 
         for v in &self.model_from_trs.constants {
-            if !self.own_constants.contains(v){
-                self.parser.add_error(format!("Константа {} была объявлена в TRS, но её нет в интерпретации", v))
+            if !self.partial && !self.own_constants.contains(v) && !failed.contains(v) {
+                let e = self.parser.error_at(ParseErrorKind::MissingConstantInterpretation).symbol(v);
+                self.parser.add_error(e);
             }
         } //non fatal
 
@@ -80,107 +84,130 @@ impl Parse for ParserInterpret {
 }
 
 impl ParserInterpret {
-    fn parse_function_or_const(&mut self) -> Result<ParsedInterpretFunction, String> {
-        let name= match self.parser.peek(){
+    fn parse_line(&mut self) -> Result<ParsedInterpretFunction, ParseError> {
+        let function = self.parse_function_or_const()?;
+        if self.parser.peek().is_ok() {
+            self.parser.read_eol()?;
+        }
+        Ok(function)
+    }
+
+    fn parse_function_or_const(&mut self) -> Result<ParsedInterpretFunction, ParseError> {
+        let received = match self.parser.peek(){
             Ok(received) => received,
-            Err(_) => return Err(self.parser.format_eof_error("функция или константа".to_string()))
+            Err(_) => return Err(self.parser.eof_error("функция или константа"))
         };
 
+        let name = match self.parser.peek_identifier() {
+            Some(name) => name,
+            None => return Err(ParseError::new(ParseErrorKind::UnknownSymbol, self.parser.current_char_span()).found(received)),
+        };
         if self.model_from_trs.functions.contains_key(&name) {
             return self.parse_function()
         } else if self.model_from_trs.constants.contains(&name) {
             return self.parse_constant()
         }
 
-        Err(format!("{}Ожидалась функция или константа, считано {}", self.parser.format_position(), name))
+        let start = self.parser.position();
+        self.parser.read_identifier("функция или константа")?;
+        Err(ParseError::new(ParseErrorKind::UnknownSymbol, Span::new(start, self.parser.last_end())).found(name))
     }
 
-    fn parse_function(&mut self) -> Result<ParsedInterpretFunction, String> {
-        let pos;
-        let name = match self.parser.peek(){
-            Ok(received) => {
-                pos = self.parser.format_position();
-                self.parser.next()?;
-                received
-            },
-            Err(_) => return Err(self.parser.format_eof_error("функция".to_string()))
-        };
+    fn parse_function(&mut self) -> Result<ParsedInterpretFunction, ParseError> {
+        self.parser.peek().map_err(|_| self.parser.eof_error("функция"))?;
+        let start = self.parser.position();
+        let name = self.parser.read_identifier("функция")?;
+        let name_span = Span::new(start, self.parser.last_end());
 
         if !self.model_from_trs.functions.contains_key(&name) {
-            self.parser.add_error(format!("{}Функция {} не объявлена в TRS", pos, name));
+            self.parser.add_error(ParseError::new(ParseErrorKind::UndeclaredFunction, name_span).symbol(&name));
         } // non fatal
 
         //skip (
         self.parser.read_exact_char('(')?;
 
         let (variables, num_of_variables) = self.parse_function_arguments()?;
-        if num_of_variables != *self.model_from_trs.functions.get(&name).unwrap() {
-            let pos = self.parser.format_position();
-            self.parser.add_error(format!("{}Количество переменных в интерпретации функции {} не совпадает с количеством переменных в TRS",
-                                          pos, name));
+        let expected_arity = *self.model_from_trs.functions.get(&name).unwrap();
+        if num_of_variables != expected_arity {
+            let e = ParseError::new(ParseErrorKind::InterpretationArityMismatch, Span::new(start, self.parser.last_end()))
+                .symbol(&name)
+                .expected(expected_arity)
+                .found(num_of_variables);
+            self.parser.add_error(e);
         } // non fatal
 
         //skip =
         self.parser.read_exact_char('=')?;
 
-        let expression = self.parse_polynomial_expression(&variables)?;
+        self.parser.peek().map_err(|_| self.parser.eof_error("описание монома"))?;
+        let expression_start = self.parser.position();
+        let (expression, definition) = self.parse_expression(&variables)?;
+        if let Some((assignment, value)) = definition.find_negative_value() {
+            let values: Vec<String> = assignment.iter().map(|(v, value)| format!("{} = {}", v, value)).collect();
+            let e = ParseError::new(ParseErrorKind::NegativeInterpretation, Span::new(expression_start, self.parser.last_end()))
+                .symbol(&name)
+                .found(value)
+                .expected(values.join(", "));
+            self.parser.add_error(e);
+        } // non fatal
 
-        if self.own_functions.contains_key(&name) {
-            self.parser.add_error(format!("{}Интерпретация функции {} уже была определена",
-                                          pos, name));
+        if let Entry::Vacant(e) = self.own_functions.entry(name.clone()) {
+            e.insert(num_of_variables);
         } else {
-            self.own_functions.insert(name, num_of_variables);
+            self.parser.add_error(ParseError::new(ParseErrorKind::DuplicateFunctionInterpretation, name_span).symbol(&name));
         }
 
         Ok(ParsedInterpretFunction{
-            name: name.to_string(),
-            variables: variables.into_iter().collect(),
-            expression: expression.to_string(),
+            name,
+            variables,
+            expression,
+            polynomial: definition.as_polynomial().cloned().unwrap_or_default(),
+            piecewise: definition.as_polynomial().is_none().then_some(definition),
+            span: Span::new(start, self.parser.last_end()),
         })
     }
 
-    fn parse_constant(&mut self) -> Result<ParsedInterpretFunction, String> {
-        let pos;
-        let name = match self.parser.peek(){
-            Ok(received) => {
-                pos = self.parser.format_position();
-                self.parser.next()?;
-                received
-            },
-            Err(_) => return Err(self.parser.format_eof_error("константа".to_string()))
-        };
+    fn parse_constant(&mut self) -> Result<ParsedInterpretFunction, ParseError> {
+        self.parser.peek().map_err(|_| self.parser.eof_error("константа"))?;
+        let start = self.parser.position();
+        let name = self.parser.read_identifier("константа")?;
+        let name_span = Span::new(start, self.parser.last_end());
 
         if !self.model_from_trs.constants.contains(&name) {
-            self.parser.add_error(format!("{}Константы {} нет в TRS, но она присутствует в интерпретации", pos, name));
+            self.parser.add_error(ParseError::new(ParseErrorKind::UndeclaredConstant, name_span).symbol(&name));
         } //non fatal
 
         self.parser.read_exact_char('=')?;
 
-        let number = self.parse_number_string()?;
+        let (number, value) = self.parse_coefficient()?;
 
         if self.own_constants.contains(&name) {
-            self.parser.add_error(format!("{}Интерпретация константы {} уже была определена",
-                                          pos, name));
+            self.parser.add_error(ParseError::new(ParseErrorKind::DuplicateConstantInterpretation, name_span).symbol(&name));
         } else {
-            self.own_constants.insert(name);
+            self.own_constants.insert(name.clone());
         }
 
         Ok(ParsedInterpretFunction{
-            name: name.to_string(),
+            name,
             variables: Vec::new(),
-            expression: format!("({})", number.to_string()),
+            expression: format!("({})", number),
+            polynomial: Polynomial::rational(value),
+            piecewise: None,
+            span: Span::new(start, self.parser.last_end()),
         })
     }
 
-    fn parse_number_string(&mut self) -> Result<String, String> {
+    fn parse_number_string(&mut self) -> Result<(String, Span), ParseError> {
         let mut number = Vec::new();
-        let mut pos= "0".to_string();
+        let mut start = self.parser.position();
 
         loop {
             match self.parser.peek() {
                 Err(_) => break,
                 Ok(digit) => {
-                    pos = self.parser.format_position();
+                    if number.is_empty() {
+                        start = self.parser.position();
+                    }
                     if !digit.is_ascii_digit() {
                         break;
                     }
@@ -192,170 +219,288 @@ impl ParserInterpret {
         }
 
         let number_string = number.join("");
+        let span = match number.is_empty() {
+            true => Span::new(start, start),
+            false => Span::new(start, self.parser.last_end()),
+        };
 
         if number_string == "0" {
-            self.parser.add_error(format!("{}Коэффициент не может быть равен 0", pos));
+            self.parser.add_error(ParseError::new(ParseErrorKind::ZeroCoefficient, span));
         };
 
-        Ok(number_string)
+        Ok((number_string, span))
     }
 
-    fn parse_variable(&mut self) -> Result<String, String> {
-        let pos;
+    /// Целое число произвольной длины или дробь `p/q` с положительным знаменателем.
+    fn parse_coefficient(&mut self) -> Result<(String, Coefficient), ParseError> {
+        let (numerator, span) = self.parse_number_string()?;
+        let numerator_value = self.parse_big_integer(&numerator, span)?;
+        if !matches!(self.parser.peek(), Ok('/')) {
+            return Ok((numerator, Coefficient::from_integer(numerator_value)));
+        }
+
+        self.parser.next()?;
+        self.parser.peek().map_err(|_| self.parser.eof_error("знаменатель"))?;
+        let start = self.parser.position();
+        let negative = matches!(self.parser.peek(), Ok('-'));
+        if negative {
+            self.parser.next()?;
+        }
+        let denominator = self.parser.read_token("знаменатель", |c, _| c.is_ascii_digit())?;
+        let span = Span::new(start, self.parser.last_end());
+        let denominator_value = BigInt::from_str(&denominator).unwrap_or_default();
+        if negative || denominator_value.is_zero() {
+            let found = if negative { format!("-{}", denominator) } else { denominator };
+            return Err(ParseError::new(ParseErrorKind::InvalidDenominator, span).found(found));
+        }
+        Ok((format!("{}/{}", numerator, denominator), Coefficient::new(numerator_value, denominator_value)))
+    }
+
+    fn parse_big_integer(&mut self, number: &str, span: Span) -> Result<BigInt, ParseError> {
+        if number.is_empty() {
+            return Err(match self.parser.peek() {
+                Ok(_) => self.parser.unexpected_symbol_error("число"),
+                Err(_) => self.parser.eof_error("число"),
+            });
+        }
+        BigInt::from_str(number).map_err(|_| ParseError::new(ParseErrorKind::NumberTooLarge, span).found(number))
+    }
+
+    fn parse_integer(&mut self, number: &str, span: Span) -> Result<i64, ParseError> {
+        if number.is_empty() {
+            return Err(match self.parser.peek() {
+                Ok(_) => self.parser.unexpected_symbol_error("число"),
+                Err(_) => self.parser.eof_error("число"),
+            });
+        }
+        number.parse::<i64>()
+            .map_err(|_| ParseError::new(ParseErrorKind::NumberTooLarge, span).found(number))
+    }
+
+    fn parse_variable(&mut self) -> Result<String, ParseError> {
         let name = match self.parser.peek(){
-            Ok(received) => {
-                pos = self.parser.format_position();
-                self.parser.next()?;
-                received
-            },
-            Err(_) => return Err(self.parser.format_eof_error("переменная".to_string()))
+            Ok(received) => received,
+            Err(_) => return Err(self.parser.eof_error("переменная"))
         };
 
         if !name.is_alphabetic() {
-            return Err(format!("{}Ожидался символ алфавита (буква) в названии переменной, считано: {}",
-                               pos, name))
+            self.parser.next()?;
+            return Err(ParseError::new(ParseErrorKind::InvalidVariableName, self.parser.previous_span()).found(name))
         }
 
-        Ok(name.to_string())
+        self.parser.read_identifier("переменная")
     }
 
-    fn parse_function_arguments(&mut self) -> Result<(HashSet<String>, i32), String> {
-        let mut variables = HashSet::new();
+    // Переменная внутри монома: самое длинное из имён аргументов, с которого начинается
+    // остаток строки, поэтому `xy` при аргументах x, y читается как x * y
+    fn parse_monomial_variable(&mut self, variables: &[String]) -> Result<String, ParseError> {
+        let matched = variables
+            .iter()
+            .filter(|v| self.parser.starts_with(v))
+            .max_by_key(|v| v.len())
+            .cloned();
+
+        match matched {
+            Some(variable) => {
+                self.parser.read_exact_str(&variable)?;
+                Ok(variable)
+            }
+            None => {
+                let variable = self.parse_variable()?;
+                Err(ParseError::new(ParseErrorKind::UnknownVariable, self.parser.previous_span()).symbol(variable))
+            }
+        }
+    }
+
+    fn parse_function_arguments(&mut self) -> Result<(Vec<String>, i32), ParseError> {
+        let mut variables = Vec::new();
         let mut num_of_variables = 0;
         loop {
-            let current = self.parse_variable()?.to_string();
-            if self.model_from_trs.functions.contains_key(&current.chars().nth(0).unwrap()){
-                let e = self.parser.format_type_error(Types::VARIABLE, Types::FUNCTION);
+            let current = self.parse_variable()?;
+            if self.model_from_trs.functions.contains_key(&current){
+                let e = self.parser.type_error(Types::VARIABLE, Types::FUNCTION);
                 self.parser.add_error(e);
-            } else if self.own_constants.contains(&current.chars().nth(0).unwrap()){
-                let e = self.parser.format_type_error(Types::VARIABLE, Types::CONSTANT);
+            } else if self.own_constants.contains(&current){
+                let e = self.parser.type_error(Types::VARIABLE, Types::CONSTANT);
                 self.parser.add_error(e);
             } //non fatal
 
             if variables.contains(&current) {
-                let pos = self.parser.format_position();
-                self.parser.add_error(format!("{}Переменная {} уже была указана в числе аргументов данной функции", pos, current));
+                let e = ParseError::new(ParseErrorKind::DuplicateArgument, self.parser.previous_span()).symbol(&current);
+                self.parser.add_error(e);
             } //non fatal
-            variables.insert(current);
+            variables.push(current);
             num_of_variables += 1;
 
-            let punctuation = match self.parser.next(){
+            let punctuation = match self.parser.peek(){
                 Ok(received) => received,
-                Err(_) => return Err(self.parser.format_eof_error("')' или ','".to_string()))
+                Err(_) => return Err(self.parser.eof_error("')' или ','"))
             };
 
-
             if punctuation == ')' {
+                self.parser.next()?;
                 return Ok((variables, num_of_variables));
             } else if punctuation != ',' {
-                return Err(format!("{}Ожидалось ',' или ')', считано '{}'",
-                                    self.parser.format_position(), punctuation));
+                return Err(self.parser.unexpected_symbol_error("',' или ')'"));
             }
+            self.parser.next()?;
         }
     }
 
-    fn parse_polynomial_expression(&mut self, variables: &HashSet<String>) -> Result<String, String> {
-        let mut polynomial_parts = Vec::new();
-
-        loop {
-            polynomial_parts.push(self.parse_monomial(variables)?);
-
-            match self.parser.peek() {
-                Err(_) => break,
-                Ok(punctuation) => {
-                    if punctuation == '\n' || punctuation == '\r' {
-                        break;
-                    } else if punctuation != '+' {
-                        return Err(format!("{}Ожидалось '+' or eol, считано '{}'",
-                                           self.parser.format_position(), punctuation));
-                    } else {
-                        self.parser.read_exact_char('+')?;
-                    }
-                }
-            }
+    /// Выражение до конца строки: сумма и разность произведений, степени `e{k}`,
+    /// скобки, `max(...)` и `min(...)`. Возвращает запись выражения и его нормальную форму.
+    fn parse_expression(&mut self, variables: &[String]) -> Result<(String, Expression), ParseError> {
+        let (part, expression) = self.parse_sum(variables)?;
+        match self.parser.peek() {
+            Err(_) | Ok('\n') | Ok('\r') => Ok((format!("({})", part), expression)),
+            Ok(_) => Err(self.parser.unexpected_symbol_error("'+', '-' или конец строки")),
         }
-
-        Ok(format!("({})", polynomial_parts.join(" + ")))
     }
 
-    fn parse_monomial(&mut self, variables: &HashSet<String>) -> Result<String, String> {
-        let mut monomial_parts = Vec::new();
-        let mut coefficient = String::new();
-        let mut symbol : char;
-        match self.parser.peek() {
-            Ok(c) => symbol = c,
-            Err(_) => return Err(self.parser.format_eof_error("описание монома".to_string())),
+    fn parse_sum(&mut self, variables: &[String]) -> Result<(String, Expression), ParseError> {
+        let mut rendered = String::new();
+        let mut result = Expression::constant(0);
+        let mut negative = false;
+        if let Ok('-') = self.parser.peek() {
+            self.parser.next()?;
+            rendered.push('-');
+            negative = true;
         }
 
-        if symbol.is_ascii_digit() {
-            coefficient = self.parse_number_string()?;
+        loop {
+            let (part, product) = self.parse_product(variables)?;
+            rendered.push_str(&part);
+            result = match negative {
+                true => result.sub(&product),
+                false => result.add(&product),
+            };
 
             match self.parser.peek() {
-                Err(_) => return Ok(coefficient),
-                Ok(symbol) => {
-                    if symbol != '*' {
-                        return Ok(coefficient);
-                    }
-
-                    self.parser.read_exact_char('*')?;
+                Ok('+') => {
+                    self.parser.next()?;
+                    rendered.push_str(" + ");
+                    negative = false;
                 }
+                Ok('-') => {
+                    self.parser.next()?;
+                    rendered.push_str(" - ");
+                    negative = true;
+                }
+                _ => return Ok((rendered, result)),
             }
         }
+    }
 
-        let mut variable = String::new();
-        let mut degree = String::new();
+    // Множители записываются через '*' или подряд: `2*xy`, `x{3}(x + 1)`; число внутри монома
+    // должно заканчиваться '*': `xy4*xy3`, но не `x2`
+    fn parse_product(&mut self, variables: &[String]) -> Result<(String, Expression), ParseError> {
+        let mut parts = Vec::new();
+        let mut result = Expression::constant(1);
+        let mut coefficient = false;
 
+        let start = self.parser.position();
         loop {
-            match self.parse_variable() {
-                Ok(name) => variable = name,
-                Err(e) => if coefficient == "" {
-                    return Err(format!("{}Ожидался коэффицент, имя переменной, '+' или перевод строки", self.parser.format_previous_position()))
-                } else {
-                    return Err(e)
-                }
+            let (part, factor) = self.parse_power(variables)?;
+            parts.push(part);
+            if result.size().saturating_mul(factor.size()) > MAX_EXPRESSION_SIZE {
+                return Err(ParseError::new(ParseErrorKind::ExpressionTooLarge, Span::new(start, self.parser.last_end()))
+                    .expected(MAX_EXPRESSION_SIZE));
             }
+            result = result.mul(&factor);
 
-            if !variables.contains(&variable) {
-                return Err(format!("{}Переменная {} не указана в качестве аргумента функции", self.parser.format_previous_position(), variable));
+            if coefficient {
+                self.parser.read_exact_char('*')?;
+                coefficient = false;
+                continue;
             }
-
             match self.parser.peek() {
-                Err(_) => {
-                    monomial_parts.push(build_monomial(&coefficient, &variable, &degree));
-                    return Ok(monomial_parts.join(" * "));
-                },
-                Ok(symbol) => {
-                    if symbol == '{' {
-                        degree = self.parse_degree()?;
-                    }
-                }
+                Ok('*') => { self.parser.next()?; }
+                Ok(c) if c.is_alphabetic() || c == '(' => (),
+                Ok(c) if c.is_ascii_digit() => coefficient = true,
+                _ => return Ok((parts.join(" * "), result)),
             }
+        }
+    }
 
-            monomial_parts.push(build_monomial(&coefficient, &variable, &degree));
+    fn parse_power(&mut self, variables: &[String]) -> Result<(String, Expression), ParseError> {
+        let start = self.parser.position();
+        let (part, base) = self.parse_atom(variables)?;
+        if let Ok('{') = self.parser.peek() {
+            let (degree, degree_span) = self.parse_degree()?;
+            let value = self.parse_integer(&degree, degree_span)?;
+            if value > MAX_DEGREE as i64 {
+                return Err(ParseError::new(ParseErrorKind::DegreeTooLarge, degree_span).found(value).expected(MAX_DEGREE));
+            }
+            let exponent = value as u32;
+            if power_size(base.size(), exponent) > MAX_EXPRESSION_SIZE {
+                return Err(ParseError::new(ParseErrorKind::ExpressionTooLarge, Span::new(start, self.parser.last_end()))
+                    .expected(MAX_EXPRESSION_SIZE));
+            }
+            return Ok((format!("{}^{}", part, degree), base.pow(exponent)));
+        }
+        Ok((part, base))
+    }
 
-            match self.parser.peek() {
-                Err(_) => return Ok(monomial_parts.join(" * ")),
-                Ok(picked_symbol) => {
-                    symbol = picked_symbol;
+    fn parse_atom(&mut self, variables: &[String]) -> Result<(String, Expression), ParseError> {
+        let symbol = match self.parser.peek() {
+            Ok(c) => c,
+            Err(_) => return Err(self.parser.eof_error("описание монома")),
+        };
 
-                    if symbol == '+' || symbol == '\n' || symbol == '\r' {
-                        return Ok(monomial_parts.join(" * "));
-                    }
-                }
+        if symbol.is_ascii_digit() {
+            let (coefficient, value) = self.parse_coefficient()?;
+            return Ok((coefficient, Expression::Polynomial(Polynomial::rational(value))));
+        }
+        if symbol == '(' {
+            self.parser.next()?;
+            let (part, expression) = self.parse_sum(variables)?;
+            self.parser.read_exact_char(')')?;
+            return Ok((format!("({})", part), expression));
+        }
+        for name in ["max", "min"] {
+            if self.parser.starts_with(&format!("{}(", name)) {
+                self.parser.read_exact_str(name)?;
+                return self.parse_max_min(name, variables);
             }
+        }
 
-            coefficient.clear();
-            degree.clear();
+        match self.parse_monomial_variable(variables) {
+            Ok(variable) => Ok((variable.clone(), Expression::variable(&variable))),
+            Err(e) if e.kind == ParseErrorKind::InvalidVariableName => Err(ParseError::new(ParseErrorKind::UnexpectedSymbol, e.span)
+                .expected("коэффициент, имя переменной, '(', max или min")
+                .found(e.found.unwrap_or_default())),
+            Err(e) => Err(e),
+        }
+    }
 
-            if symbol.is_ascii_digit() {
-                coefficient = self.parse_number_string()?;
-                self.parser.read_exact_char('*')?;
+    // Имя max или min уже считано
+    fn parse_max_min(&mut self, name: &str, variables: &[String]) -> Result<(String, Expression), ParseError> {
+        self.parser.read_exact_char('(')?;
+        let mut parts = Vec::new();
+        let mut arguments = Vec::new();
+        loop {
+            let (part, argument) = self.parse_sum(variables)?;
+            parts.push(part);
+            arguments.push(argument);
+            match self.parser.peek() {
+                Ok(',') => { self.parser.next()?; }
+                Ok(')') => {
+                    self.parser.next()?;
+                    break;
+                }
+                Ok(_) => return Err(self.parser.unexpected_symbol_error("',' или ')'")),
+                Err(_) => return Err(self.parser.eof_error("')'")),
             }
-
         }
+
+        let expression = match name {
+            "max" => Expression::max(arguments),
+            _ => Expression::min(arguments),
+        };
+        Ok((format!("{}({})", name, parts.join(", ")), expression))
     }
 
-    fn parse_degree(&mut self) -> Result<String, String> {
+    fn parse_degree(&mut self) -> Result<(String, Span), ParseError> {
         self.parser.read_exact_char('{')?;
         let degree = self.parse_number_string()?;
         self.parser.read_exact_char('}')?;
@@ -363,18 +508,3 @@ impl ParserInterpret {
         Ok(degree)
     }
 }
-
-fn build_monomial (coefficient: &String, variable: &String, degree: &String) -> String {
-    let mut monomial = variable.clone();
-
-    if coefficient != "" {
-        monomial = format!("{} * {}", coefficient, monomial);
-    }
-
-    if degree != "" {
-        monomial = format!("{}^{}", monomial, degree);
-    }
-
-    monomial
-}
-
@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+use super::Parse;
+use crate::models::{ParseError, ParseErrorKind, ParsedData, Parser};
+use crate::models::data_structures::{MatrixInterpretFunction, Model, Position, Span, SquareMatrix, Types, Vector};
+
+/// Разбор матричной интерпретации:
+/// ```text
+/// f(x, y) = [1, 0] + [[1, 1], [0, 1]] * x + [[1, 0], [0, 0]] * y
+/// a = [1, 1]
+/// ```
+/// Размерность задаётся первым вектором или матрицей. Слагаемые одного вида складываются;
+/// аргумент без матрицы получает нулевую матрицу, функция без вектора — нулевой вектор.
+#[derive(Debug)]
+pub struct ParserMatrix {
+    parser: Parser,
+    model_from_trs: Model,
+    dimension: Option<usize>,
+    own_functions: HashSet<String>,
+    own_constants: HashSet<String>,
+}
+
+impl ParserMatrix {
+    pub fn new(input: &str, model: Model) -> Self {
+        ParserMatrix {
+            parser: Parser::new(input),
+            model_from_trs: model,
+            dimension: None,
+            own_functions: HashSet::new(),
+            own_constants: HashSet::new(),
+        }
+    }
+}
+
+impl Parse for ParserMatrix {
+    fn parse(&mut self) -> Result<ParsedData, Vec<ParseError>> {
+        let mut result = Vec::new();
+
+        loop {
+            self.parser.skip_whitespace();
+            match self.parse_symbol() {
+                Ok(function) => result.push(function),
+                Err(e) => {
+                    self.parser.add_fatal_error(e);
+                    return Err(self.parser.get_errors());
+                }
+            }
+
+            if let Err(e) = self.parser.read_eol() {
+                self.parser.add_fatal_error(e);
+                return Err(self.parser.get_errors());
+            }
+            self.parser.skip_whitespace();
+            if self.parser.peek().is_err() {
+                break;
+            }
+        }
+
+        let mut functions: Vec<&String> = self.model_from_trs.functions.keys().collect();
+        functions.sort();
+        for k in functions {
+            if !self.own_functions.contains(k) {
+                let e = self.parser.error_at(ParseErrorKind::MissingFunctionInterpretation).symbol(k);
+                self.parser.add_error(e);
+            }
+        } //non fatal
+
+        let mut constants: Vec<&String> = self.model_from_trs.constants.iter().collect();
+        constants.sort();
+        for v in constants {
+            if !self.own_constants.contains(v) {
+                let e = self.parser.error_at(ParseErrorKind::MissingConstantInterpretation).symbol(v);
+                self.parser.add_error(e);
+            }
+        } //non fatal
+
+        if !self.parser.get_errors().is_empty() {
+            return Err(self.parser.get_errors());
+        }
+
+        let dimension = self.dimension.unwrap_or(1);
+        for function in &mut result {
+            if function.constant.is_empty() {
+                function.constant = vec![0; dimension];
+            }
+            for matrix in &mut function.matrices {
+                if matrix.is_empty() {
+                    *matrix = vec![vec![0; dimension]; dimension];
+                }
+            }
+        }
+        Ok(ParsedData::Matrix(result))
+    }
+}
+
+impl ParserMatrix {
+    fn parse_symbol(&mut self) -> Result<MatrixInterpretFunction, ParseError> {
+        self.parser.peek().map_err(|_| self.parser.eof_error("функция или константа"))?;
+        let start = self.parser.position();
+        let name = self.parser.read_identifier("функция или константа")?;
+        let name_span = Span::new(start, self.parser.last_end());
+
+        let arity = if let Some(arity) = self.model_from_trs.functions.get(&name) {
+            *arity
+        } else if self.model_from_trs.constants.contains(&name) {
+            0
+        } else {
+            return Err(ParseError::new(ParseErrorKind::UnknownSymbol, name_span).found(name));
+        };
+
+        let variables = match self.parser.peek() {
+            Ok('(') => {
+                self.parser.next()?;
+                self.parse_arguments()?
+            }
+            _ => Vec::new(),
+        };
+        if variables.len() as i32 != arity {
+            let e = ParseError::new(ParseErrorKind::InterpretationArityMismatch, Span::new(start, self.parser.last_end()))
+                .symbol(&name)
+                .expected(arity)
+                .found(variables.len());
+            self.parser.add_error(e);
+        } // non fatal
+
+        self.parser.read_exact_char('=')?;
+        let (constant, matrices) = self.parse_sum(&variables)?;
+
+        let (own, kind) = match arity {
+            0 => (&mut self.own_constants, ParseErrorKind::DuplicateConstantInterpretation),
+            _ => (&mut self.own_functions, ParseErrorKind::DuplicateFunctionInterpretation),
+        };
+        if !own.insert(name.clone()) {
+            self.parser.add_error(ParseError::new(kind, name_span).symbol(&name));
+        } // non fatal
+
+        Ok(MatrixInterpretFunction {
+            name,
+            variables,
+            constant,
+            matrices,
+            span: Span::new(start, self.parser.last_end()),
+        })
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut variables = Vec::new();
+        loop {
+            let current = self.parser.read_identifier("переменная")?;
+            if self.model_from_trs.functions.contains_key(&current) {
+                let e = self.parser.type_error(Types::VARIABLE, Types::FUNCTION);
+                self.parser.add_error(e);
+            } else if self.model_from_trs.constants.contains(&current) {
+                let e = self.parser.type_error(Types::VARIABLE, Types::CONSTANT);
+                self.parser.add_error(e);
+            } //non fatal
+
+            if variables.contains(&current) {
+                let e = ParseError::new(ParseErrorKind::DuplicateArgument, self.parser.previous_span()).symbol(&current);
+                self.parser.add_error(e);
+            } //non fatal
+            variables.push(current);
+
+            match self.parser.peek() {
+                Ok(')') => {
+                    self.parser.next()?;
+                    return Ok(variables);
+                }
+                Ok(',') => { self.parser.next()?; }
+                Ok(_) => return Err(self.parser.unexpected_symbol_error("',' или ')'")),
+                Err(_) => return Err(self.parser.eof_error("')' или ','")),
+            }
+        }
+    }
+
+    /// Сумма векторов и произведений `матрица * аргумент`.
+    fn parse_sum(&mut self, variables: &[String]) -> Result<(Vector, Vec<SquareMatrix>), ParseError> {
+        let mut constant = Vec::new();
+        let mut matrices = vec![Vec::new(); variables.len()];
+
+        loop {
+            self.parser.peek().map_err(|_| self.parser.eof_error("'['"))?;
+            let start = self.parser.position();
+            self.parser.read_exact_char('[')?;
+            if let Ok('[') = self.parser.peek() {
+                let matrix = self.parse_matrix_rows(start)?;
+                self.parser.read_exact_char('*')?;
+                let variable = self.parser.read_identifier("переменная")?;
+                let i = match variables.iter().position(|v| *v == variable) {
+                    Some(i) => i,
+                    None => return Err(ParseError::new(ParseErrorKind::UnknownVariable, self.parser.previous_span()).symbol(variable)),
+                };
+                add_matrix(&mut matrices[i], matrix, Span::new(start, self.parser.last_end()))?;
+            } else {
+                let vector = self.parse_vector_rest()?;
+                let span = Span::new(start, self.parser.last_end());
+                self.check_dimension(vector.len(), span);
+                add_vector(&mut constant, &vector, span)?;
+            }
+
+            match self.parser.peek() {
+                Ok('+') => { self.parser.next()?; }
+                Ok('\n') | Ok('\r') | Err(_) => return Ok((constant, matrices)),
+                Ok(_) => return Err(self.parser.unexpected_symbol_error("'+' или конец строки")),
+            }
+        }
+    }
+
+    // Открывающая скобка матрицы уже считана
+    fn parse_matrix_rows(&mut self, start: Position) -> Result<SquareMatrix, ParseError> {
+        let mut rows = Vec::new();
+        loop {
+            self.parser.read_exact_char('[')?;
+            rows.push(self.parse_vector_rest()?);
+            match self.parser.peek() {
+                Ok(',') => { self.parser.next()?; }
+                Ok(']') => {
+                    self.parser.next()?;
+                    break;
+                }
+                Ok(_) => return Err(self.parser.unexpected_symbol_error("',' или ']'")),
+                Err(_) => return Err(self.parser.eof_error("']'")),
+            }
+        }
+
+        let span = Span::new(start, self.parser.last_end());
+        self.check_dimension(rows.len(), span);
+        if let Some(row) = rows.iter().find(|row| row.len() != rows.len()) {
+            let e = ParseError::new(ParseErrorKind::MatrixDimensionMismatch, span)
+                .expected(format!("{}x{}", rows.len(), rows.len()))
+                .found(format!("{}x{}", rows.len(), row.len()));
+            self.parser.add_error(e);
+        } // non fatal
+        Ok(rows)
+    }
+
+    // Открывающая скобка вектора уже считана
+    fn parse_vector_rest(&mut self) -> Result<Vector, ParseError> {
+        let mut vector = Vec::new();
+        loop {
+            let number = self.parser.read_token("число", |c, _| c.is_ascii_digit())?;
+            let value = number
+                .parse::<i64>()
+                .map_err(|_| ParseError::new(ParseErrorKind::NumberTooLarge, self.parser.previous_span()).found(&number))?;
+            vector.push(value);
+            match self.parser.peek() {
+                Ok(',') => { self.parser.next()?; }
+                Ok(']') => {
+                    self.parser.next()?;
+                    return Ok(vector);
+                }
+                Ok(_) => return Err(self.parser.unexpected_symbol_error("',' или ']'")),
+                Err(_) => return Err(self.parser.eof_error("']'")),
+            }
+        }
+    }
+
+    fn check_dimension(&mut self, found: usize, span: Span) {
+        match self.dimension {
+            None => self.dimension = Some(found),
+            Some(dimension) if dimension != found => {
+                let e = ParseError::new(ParseErrorKind::MatrixDimensionMismatch, span)
+                    .expected(dimension)
+                    .found(found);
+                self.parser.add_error(e);
+            } // non fatal
+            Some(_) => (),
+        }
+    }
+}
+
+// `span` — слагаемое, при прибавлении которого произошло переполнение
+fn add_vector(sum: &mut Vector, vector: &[i64], span: Span) -> Result<(), ParseError> {
+    if sum.is_empty() {
+        *sum = vector.to_vec();
+        return Ok(());
+    }
+    for (a, b) in sum.iter_mut().zip(vector) {
+        *a = a.checked_add(*b)
+            .ok_or_else(|| ParseError::new(ParseErrorKind::NumberTooLarge, span).found(format!("{} + {}", a, b)))?;
+    }
+    Ok(())
+}
+
+fn add_matrix(sum: &mut SquareMatrix, matrix: SquareMatrix, span: Span) -> Result<(), ParseError> {
+    if sum.is_empty() {
+        *sum = matrix;
+        return Ok(());
+    }
+    for (row, other) in sum.iter_mut().zip(&matrix) {
+        add_vector(row, other, span)?;
+    }
+    Ok(())
+}
@@ -1,50 +1,64 @@
 use super::Parse;
-use crate::models::{ParsedData, ParsedDataTRS, Parser};
+use crate::models::{ParseError, ParseErrorKind, ParsedData, ParsedDataTRS, Parser};
 use std::collections::{HashMap, HashSet};
-use crate::models::data_structures::{Rule, Term, Types};
+use crate::models::data_structures::{Rule, Span, Term, Types};
+
+/// Синтаксис входной TRS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrsFormat {
+    /// `variables = x, y` и правила вида `f(x) = g(x)`, по одному на строке.
+    #[default]
+    Course,
+    /// Формат TPDB: `(VAR x y) (RULES f(x,y) -> g(x))`.
+    Tpdb,
+}
 
 #[derive(Debug)]
 pub struct ParserTRS {
     parser: Parser,
-    pub variables: HashSet<char>,
-    pub constants: HashSet<char>,
-    pub functions: HashMap<char, i32>,
-    pub left_variables: HashSet<char>,
-    pub right_variables: HashSet<char>,
-}
-
-#[derive(Copy, Clone)]
-enum RuleType {
-    LEFT, RIGHT
+    format: TrsFormat,
+    pub variables: HashSet<String>,
+    pub constants: HashSet<String>,
+    pub functions: HashMap<String, i32>,
 }
 
 impl ParserTRS {
     pub fn new(input: &str) -> Self {
+        ParserTRS::with_format(input, TrsFormat::Course)
+    }
+
+    pub fn with_format(input: &str, format: TrsFormat) -> Self {
         ParserTRS {
-            parser: Parser::new(input),
+            // В TPDB свои комментарии (раздел COMMENT), а `#` и `--` могут входить в символы
+            parser: match format {
+                TrsFormat::Course => Parser::new(input),
+                TrsFormat::Tpdb => Parser::without_comments(input),
+            },
+            format,
             variables: HashSet::new(),
             constants: HashSet::new(),
             functions: HashMap::new(),
-            left_variables: HashSet::new(),
-            right_variables: HashSet::new(),
         }
     }
 
-    fn parse_variables(&mut self) -> Result<(), String> {
+    fn parse_variables(&mut self) -> Result<(), ParseError> {
         let expected = "variables";
+        // Пустые строки и строки с комментариями перед объявлением пропускаются
+        self.parser.skip_whitespace();
         match self.parser.peek(){
             Ok(_) => (),
-            Err(_) => return Err(self.parser.format_eof_error("объявление переменных (variables=...)".to_string()))
+            Err(_) => return Err(self.parser.eof_error("объявление переменных (variables=...)"))
         }
 
         for c in expected.chars() {
-            let peeked: char;
-            match self.parser.peek_without_skipping(){
-                Ok(received) => peeked = received,
-                Err(_) => return Err(self.parser.format_eof_error(c.to_string()))
-            }
-            if self.parser.peek_without_skipping()? != c {
-                return Err(self.parser.format_error(c.to_string()));
+            let peeked = match self.parser.peek_without_skipping(){
+                Ok(received) => received,
+                Err(_) => return Err(self.parser.eof_error(c))
+            };
+            if peeked != c {
+                return Err(self.parser.unexpected_symbol_error(c));
             }
             self.parser.next()?;
         }
@@ -52,32 +66,32 @@ impl ParserTRS {
         // parse list of variables
         match self.parser.read_exact_char('='){
             Ok(_) => (),
-            Err(e) =>{
-                let pos = self.parser.format_position();
-
-                self.parser.add_error(format!("{}Не хватает '=' в списке переменных",
-                pos));
+            Err(_) =>{
+                let e = self.parser.error_at(ParseErrorKind::MissingVariablesEquals);
+                self.parser.add_error(e);
             }
         };
         loop {
             let peeked= match self.parser.peek(){
                 Ok(received) => received,
-                Err(_) => return Err(self.parser.format_eof_error("переменная".to_string())),
+                Err(_) => return Err(self.parser.eof_error("переменная")),
             };
             if peeked.is_alphabetic() {
-                let current_variable = self.parser.next()?;
+                let current_variable = self.parser.read_identifier("переменная")?;
                 // Non-fatal, accumulate error, no extra behaviour is necessary
-                if !self.variables.insert(current_variable) {
-                    self.parser.add_error(format!("Переменная {} объявлена несколько раз", current_variable));
+                if self.variables.contains(&current_variable) {
+                    let e = ParseError::new(ParseErrorKind::DuplicateVariable, self.parser.previous_span())
+                        .symbol(&current_variable);
+                    self.parser.add_error(e);
                 }
+                self.variables.insert(current_variable);
             } else {
                 break;
             }
-            let after_var: char;
-            match self.parser.peek(){
-                Ok(received) => after_var = received,
-                Err(_) => return Err(self.parser.format_eof_error("',' или конец строки".to_string())),
-            }
+            let after_var = match self.parser.peek(){
+                Ok(received) => received,
+                Err(_) => return Err(self.parser.eof_error("',' или конец строки")),
+            };
             if after_var == ',' {
                 self.parser.next()?;
             } else {
@@ -86,127 +100,232 @@ impl ParserTRS {
         }
         self.parser.read_eol()?;
         if self.variables.is_empty() {
-            return Err(format!("{}У функции не найдено ни одной переменной", self.parser.format_position()));
+            return Err(self.parser.error_at(ParseErrorKind::NoVariables));
         }
         Ok(())
     }
 
-    fn parse_rules(&mut self) -> Result<Vec<Rule>, String> {
+    fn parse_rules(&mut self) -> Result<Vec<Rule>, ParseError> {
         let mut rules: Vec<Rule> = Vec::new();
 
         loop {
-            match self.parser.peek() {
-                Ok(_) => {
-                    let rule = self.parse_rule()?;
-                    rules.push(rule);
+            self.parser.skip_whitespace();
+            if self.parser.peek().is_err() {
+                break;
+            }
+            let line = self.parser.position().line;
+            match self.parse_rule() {
+                Ok(rule) => rules.push(rule),
+                // Ошибка запоминается, разбор продолжается со следующей строки
+                Err(e) => {
+                    self.parser.add_fatal_error(e);
+                    // Функции, на аргументах которых разбор прервался, не получили арность
+                    self.functions.retain(|_, arity| *arity != -1);
+                    if self.parser.position().line == line {
+                        self.parser.skip_line();
+                    }
                 }
-                Err(_) => break,
             }
         }
 
-        if rules.is_empty() {
-            return Err("Правила переписывания не обнаружены".to_string());
+        if rules.is_empty() && !self.parser.has_fatal_errors() {
+            return Err(self.parser.error_at(ParseErrorKind::NoRules));
         }
-        Ok((rules))
+        Ok(rules)
     }
 
-    fn parse_rule(&mut self) -> Result<Rule, String> {
-        let lhs = self.parse_term(RuleType::LEFT)?;
+    fn parse_rule(&mut self) -> Result<Rule, ParseError> {
+        let lhs = self.parse_term()?;
 
         self.parser.read_exact_char('=')?;
 
-        let rhs = self.parse_term(RuleType::RIGHT)?;
+        let rhs = self.parse_term()?;
 
-
-        let dif = self.right_variables
-            .difference(&self.left_variables)
-            .cloned()
-            .collect::<HashSet<char>>();
-        let res = match dif.is_empty() {
-            true => Ok(Rule { left: lhs, right: rhs }),
-            false => return Err(self.parser.format_variables_count_error(dif))
-        };
+        let res = self.build_rule(lhs, rhs);
         self.parser.read_eol()?;
         res
     }
 
-    fn parse_term(&mut self, rule_type: RuleType) -> Result<Term, String> {
-        let c : char;
-        match self.parser.peek(){
-            Ok(received) => c = received,
-            Err(_) => return Err(self.parser.format_eof_error("терм".to_string()))
+    /// Проверка условия на переменные правила `l = r`: `var(r) ⊆ var(l)`.
+    fn build_rule(&mut self, lhs: Term, rhs: Term) -> Result<Rule, ParseError> {
+        let rule = Rule { span: Span::new(lhs.span.start, rhs.span.end), left: lhs, right: rhs };
+        let unbound = rule.unbound_variables(&self.variables);
+        match unbound.is_empty() {
+            true => Ok(rule),
+            false => Err(self.parser.variables_count_error(rule.span, unbound)),
         }
+    }
 
-        if !c.is_alphabetic() {
-            return Err(self.parser.format_error("буква".parse().unwrap()));
+    fn parse_tpdb(&mut self) -> Result<Vec<Rule>, ParseError> {
+        let mut rules: Vec<Rule> = Vec::new();
+        loop {
+            self.parser.skip_whitespace();
+            if self.parser.peek().is_err() {
+                break;
+            }
+            self.parser.read_exact_char('(')?;
+            let expected = "VAR, RULES или COMMENT";
+            let section = self.parser.read_token(expected, is_tpdb_symbol_char)?;
+            match section.as_str() {
+                "VAR" => self.parse_tpdb_variables()?,
+                "RULES" => self.parse_tpdb_rules(&mut rules)?,
+                "COMMENT" => self.skip_tpdb_section()?,
+                _ => return Err(ParseError::new(ParseErrorKind::UnknownSection, self.parser.previous_span())
+                    .found(section)
+                    .expected(expected)),
+            }
+            self.parser.skip_whitespace();
+            self.parser.read_exact_char(')')?;
         }
-        self.parser.next()?;
+
+        if rules.is_empty() {
+            return Err(self.parser.error_at(ParseErrorKind::NoRules));
+        }
+        Ok(rules)
+    }
+
+    fn at_section_end(&mut self) -> bool {
+        self.parser.skip_whitespace();
+        matches!(self.parser.peek(), Ok(')') | Err(_))
+    }
+
+    fn parse_tpdb_variables(&mut self) -> Result<(), ParseError> {
+        while !self.at_section_end() {
+            let variable = self.parser.read_token("переменная", is_tpdb_symbol_char)?;
+            if self.variables.contains(&variable) {
+                let e = ParseError::new(ParseErrorKind::DuplicateVariable, self.parser.previous_span())
+                    .symbol(&variable);
+                self.parser.add_error(e);
+            }
+            self.variables.insert(variable);
+        }
+        Ok(())
+    }
+
+    fn parse_tpdb_rules(&mut self, rules: &mut Vec<Rule>) -> Result<(), ParseError> {
+        while !self.at_section_end() {
+            let lhs = self.parse_term()?;
+            self.parser.skip_whitespace();
+            self.parser.read_exact_str("->")?;
+            // Относительные правила `->=` не поддерживаются
+            if self.parser.peek_without_skipping() == Ok('=') {
+                return Err(self.parser.unexpected_symbol_error("терм"));
+            }
+            let rhs = self.parse_term()?;
+            rules.push(self.build_rule(lhs, rhs)?);
+        }
+        Ok(())
+    }
+
+    /// Пропускает содержимое раздела (например, COMMENT) до парной закрывающей скобки.
+    fn skip_tpdb_section(&mut self) -> Result<(), ParseError> {
+        let mut depth = 0;
+        loop {
+            match self.parser.peek() {
+                Ok(')') if depth == 0 => return Ok(()),
+                Ok(')') => depth -= 1,
+                Ok('(') => depth += 1,
+                Ok(_) => (),
+                Err(_) => return Err(self.parser.eof_error("')'")),
+            }
+            self.parser.next()?;
+        }
+    }
+
+    /// Для TPDB переводы строк внутри терма не значимы.
+    fn peek_symbol(&mut self) -> Result<char, ParseError> {
+        if self.format == TrsFormat::Tpdb {
+            self.parser.skip_whitespace();
+        }
+        self.parser.peek()
+    }
+
+    fn read_symbol(&mut self) -> Result<String, ParseError> {
+        match self.format {
+            TrsFormat::Course => self.parser.read_identifier("буква"),
+            TrsFormat::Tpdb => self.parser.read_token("символ", is_tpdb_symbol_char),
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Term, ParseError> {
+        match self.peek_symbol(){
+            Ok(_) => (),
+            Err(_) => return Err(self.parser.eof_error("терм"))
+        };
+
+        let start = self.parser.position();
+        let name = self.read_symbol()?;
         let mut term = Term {
-            value: c.to_string(),
+            value: name.clone(),
             childs: Vec::new(),
+            span: Span::new(start, self.parser.last_end()),
         };
 
-        let symbol = match self.parser.peek() {
+        let symbol = match self.peek_symbol() {
             Ok(val) => { val }
             Err(_) => {
-                if self.functions.contains_key(&c) {
-                    return Err(self.parser.format_type_error(Types::ConstantOrVariable, Types::FUNCTION));
+                if self.functions.contains_key(&name) {
+                    return Err(self.parser.type_error(Types::ConstantOrVariable, Types::FUNCTION));
                 }
-                self.check_variable_or_const(c, rule_type);
+                self.check_variable_or_const(name);
                 return Ok(term)
             }
         };
 
-        if symbol == '(' {
-            if self.variables.contains(&c) {
-                return Err(self.parser.format_type_error(Types::FUNCTION, Types::VARIABLE));
+        // В TPDB константа может быть записана как `a()`
+        let empty_args = self.format == TrsFormat::Tpdb && self.parser.starts_with("()");
+        if empty_args {
+            self.parser.read_exact_str("()")?;
+            term.span.end = self.parser.last_end();
+        }
+
+        if symbol == '(' && !empty_args {
+            if self.variables.contains(&name) {
+                return Err(self.parser.type_error(Types::FUNCTION, Types::VARIABLE));
             }
-            if self.constants.contains(&c) {
-                return Err(self.parser.format_type_error(Types::FUNCTION, Types::CONSTANT));
+            if self.constants.contains(&name) {
+                return Err(self.parser.type_error(Types::FUNCTION, Types::CONSTANT));
             }
             self.parser.read_exact_char('(')?;
-            if !self.functions.contains_key(&c) {
-                self.functions.insert(c, -1);
-            }
-            let args = self.parse_arg_list(rule_type)?;
+            self.functions.entry(name.clone()).or_insert(-1);
+            let args = self.parse_arg_list()?;
 
-            if *self.functions.get(&c).unwrap() == -1 {
-                self.functions.insert(c, args.len() as i32);
-            } else if *self.functions.get(&c).unwrap() != args.len() as i32 {
-                return Err(self.parser.format_arity_error(c, self.functions.get(&c).unwrap().to_string(), args.len().to_string()));
+            if *self.functions.get(&name).unwrap() == -1 {
+                self.functions.insert(name, args.len() as i32);
+            } else if *self.functions.get(&name).unwrap() != args.len() as i32 {
+                let expected = *self.functions.get(&name).unwrap() as usize;
+                let span = Span::new(start, self.parser.position());
+                return Err(self.parser.arity_error(&name, span, expected, args.len()));
             }
             self.parser.read_exact_char(')')?;
             term.childs = args;
+            term.span.end = self.parser.last_end();
         } else {
-            if self.functions.contains_key(&c) {
-                return Err(self.parser.format_type_error(Types::ConstantOrVariable, Types::FUNCTION));
+            if self.functions.contains_key(&name) {
+                return Err(self.parser.type_error(Types::ConstantOrVariable, Types::FUNCTION));
             }
-            self.check_variable_or_const(c, rule_type)
+            self.check_variable_or_const(name)
         }
 
         Ok(term)
     }
 
-    fn check_variable_or_const(&mut self, c: char, rule_type: RuleType) {
-        if !self.variables.contains(&c) {
-            self.constants.insert(c);
-        } else {
-            match rule_type {
-                RuleType::LEFT => self.left_variables.insert(c),
-                RuleType::RIGHT => self.right_variables.insert(c),
-            };
+    fn check_variable_or_const(&mut self, name: String) {
+        if !self.variables.contains(&name) {
+            self.constants.insert(name);
         }
     }
-    fn parse_arg_list(&mut self, rule_type: RuleType) -> Result<Vec<Term>, String> {
+
+    fn parse_arg_list(&mut self) -> Result<Vec<Term>, ParseError> {
         let mut args: Vec<Term> = Vec::new();
-        args.push(self.parse_term(rule_type)?);
+        args.push(self.parse_term()?);
 
-        while match self.parser.peek() {
+        while match self.peek_symbol() {
             Ok(received) => received,
-            Err(_) => return Err(self.parser.format_eof_error("','".to_string())),
+            Err(_) => return Err(self.parser.eof_error("','")),
         } == ',' {
             self.parser.next()?;
-            args.push(self.parse_term(rule_type)?);
+            args.push(self.parse_term()?);
         }
 
         Ok(args)
@@ -214,28 +333,39 @@ impl ParserTRS {
 }
 
 impl Parse for ParserTRS {
-    fn parse(&mut self) -> Result<(ParsedData), Vec<String>> {
-        match self.parse_variables(){
-            Ok(_) => (),
-            Err(e) => {
-                self.parser.add_error(e);
-                return Err(self.parser.get_errors());
-            },
+    fn parse(&mut self) -> Result<ParsedData, Vec<ParseError>> {
+        let rules = match self.format {
+            TrsFormat::Course => self.parse_variables().and_then(|_| self.parse_rules()),
+            TrsFormat::Tpdb => self.parse_tpdb(),
         };
-
-        let rules = match self.parse_rules(){
+        let rules = match rules {
             Ok(rules) => rules,
             Err(e) => {
-                self.parser.add_error(e);
+                self.parser.add_fatal_error(e);
                 return Err(self.parser.get_errors());
             },
         };
 
-        Ok((ParsedData::TRS(ParsedDataTRS {
+        let trs = ParsedDataTRS {
             rules,
             variables: self.variables.clone(),
             constants: self.constants.clone(),
             functions: self.functions.clone(),
-        })))
+        };
+        // Проверяются и правила, разобранные до и после ошибок в других строках
+        for e in trs.check_well_formed(false) {
+            match e.fatal {
+                true => self.parser.add_fatal_error(e),
+                false => self.parser.add_error(e),
+            }
+        }
+        if self.parser.has_fatal_errors() {
+            return Err(self.parser.get_errors());
+        }
+        Ok(ParsedData::TRS(trs))
     }
 }
+
+fn is_tpdb_symbol_char(c: char, next: Option<char>) -> bool {
+    !(c.is_whitespace() || matches!(c, '(' | ')' | ',' | '"' | '|') || (c == '-' && next == Some('>')))
+}
@@ -0,0 +1,274 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
+use crate::models::ParsedDataTRS;
+use crate::models::data_structures::{Rule, Span, Term};
+use crate::termination::{AdmissibilityError, Kbo, Lpo};
+use super::critical_pairs::critical_pairs;
+use super::rewriter::{Rewriter, Strategy};
+use super::substitution::{apply, Substitution};
+
+// Ограничение на число шагов нормализации одного терма
+const NORMALIZE_LIMIT: usize = 10_000;
+
+/// Порядок редукции, по которому ориентируются уравнения.
+#[derive(Debug, Clone)]
+pub enum ReductionOrder {
+    Lpo(Lpo),
+    Kbo(Kbo),
+}
+
+impl ReductionOrder {
+    /// Ошибка — переполнение при вычислении весов KBO.
+    pub fn greater(&self, s: &Term, t: &Term, variables: &HashSet<String>) -> Result<bool, String> {
+        match self {
+            ReductionOrder::Lpo(lpo) => Ok(lpo.greater(s, t, variables).is_some()),
+            ReductionOrder::Kbo(kbo) => Ok(kbo.greater(s, t, variables)?.is_some()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CompletionOptions {
+    /// Ограничение на число обработанных уравнений.
+    pub max_steps: usize,
+    pub time_limit: Duration,
+}
+
+impl Default for CompletionOptions {
+    fn default() -> Self {
+        CompletionOptions { max_steps: 1000, time_limit: Duration::from_secs(10) }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum FailureReason {
+    /// Уравнение нельзя ориентировать ни в одну сторону.
+    Unorientable { left: String, right: String },
+    /// Веса KBO недопустимы для сигнатуры TRS.
+    Inadmissible { errors: Vec<AdmissibilityError> },
+    /// Переполнение при сравнении термов.
+    Overflow { message: String },
+    StepLimit { steps: usize },
+    TimeLimit,
+}
+
+/// Неудача пополнения: причина и правила, построенные к этому моменту.
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct CompletionFailure {
+    #[serde(flatten)]
+    pub reason: FailureReason,
+    pub rules: Vec<Rule>,
+}
+
+/// Пополнение по Кнуту–Бендиксу: правила `trs` рассматриваются как уравнения.
+///
+/// Уравнения обрабатываются по очереди: обе части нормализуются текущими правилами,
+/// тривиальные отбрасываются, остальные ориентируются порядком `order`. Новое правило
+/// упрощает уже построенные; когда очередь пуста, в неё добавляются несводимые
+/// критические пары. Результат — сходящаяся TRS, эквивалентная исходным уравнениям.
+pub fn complete(trs: &ParsedDataTRS, order: &ReductionOrder, options: &CompletionOptions) -> Result<ParsedDataTRS, CompletionFailure> {
+    let mut completion = Completion {
+        trs: ParsedDataTRS {
+            rules: Vec::new(),
+            variables: trs.variables.clone(),
+            constants: trs.constants.clone(),
+            functions: trs.functions.clone(),
+        },
+        variables: sorted(&trs.variables),
+    };
+
+    if let ReductionOrder::Kbo(kbo) = order {
+        let errors = kbo.admissibility(trs);
+        if !errors.is_empty() {
+            return Err(completion.failure(FailureReason::Inadmissible { errors }));
+        }
+    }
+
+    let start = Instant::now();
+    let mut steps = 0;
+    let mut equations: VecDeque<(Term, Term)> = trs.rules
+        .iter()
+        .map(|rule| (rule.left.clone(), rule.right.clone()))
+        .collect();
+    loop {
+        while let Some((s, t)) = equations.pop_front() {
+            steps += 1;
+            if steps > options.max_steps {
+                return Err(completion.failure(FailureReason::StepLimit { steps: options.max_steps }));
+            }
+            if start.elapsed() > options.time_limit {
+                return Err(completion.failure(FailureReason::TimeLimit));
+            }
+
+            let s = completion.normalize(&s);
+            let t = completion.normalize(&t);
+            if s == t {
+                continue;
+            }
+            let greater = |a: &Term, b: &Term| order
+                .greater(a, b, &completion.trs.variables)
+                .map_err(|message| completion.failure(FailureReason::Overflow { message }));
+            let (left, right) = if greater(&s, &t)? {
+                (s, t)
+            } else if greater(&t, &s)? {
+                (t, s)
+            } else {
+                return Err(completion.failure(FailureReason::Unorientable { left: s.to_string(), right: t.to_string() }));
+            };
+            equations.extend(completion.add_rule(left, right));
+        }
+
+        let pairs = critical_pairs(&completion.trs);
+        let renamed: Vec<String> = pairs
+            .iter()
+            .flat_map(|pair| term_variables(&pair.peak, &completion.trs))
+            .collect();
+        completion.trs.variables.extend(renamed);
+        for pair in pairs {
+            let left = completion.normalize(&pair.left);
+            let right = completion.normalize(&pair.right);
+            if left != right {
+                equations.push_back((left, right));
+            }
+        }
+        if equations.is_empty() {
+            return Ok(completion.result(&trs.variables));
+        }
+    }
+}
+
+struct Completion {
+    trs: ParsedDataTRS,
+    // Исходные переменные в алфавитном порядке: из них составляются имена переменных правил
+    variables: Vec<String>,
+}
+
+impl Completion {
+    fn normalize(&self, term: &Term) -> Term {
+        let rewriter = Rewriter::new(&self.trs);
+        rewriter.normalize(term, Strategy::LeftmostInnermost, NORMALIZE_LIMIT).result().clone()
+    }
+
+    /// Добавляет правило `left -> right` и упрощает им остальные правила.
+    /// Правила, левая часть которых сократилась, возвращаются как уравнения.
+    fn add_rule(&mut self, left: Term, right: Term) -> Vec<(Term, Term)> {
+        let rule = self.rename(Rule { left, right, span: Span::default() });
+        let added = ParsedDataTRS {
+            rules: vec![rule.clone()],
+            variables: self.trs.variables.clone(),
+            ..ParsedDataTRS::default()
+        };
+        let rewriter = Rewriter::new(&added);
+
+        let mut equations = Vec::new();
+        let mut rules = Vec::new();
+        for old in self.trs.rules.drain(..) {
+            match rewriter.is_normal_form(&old.left) {
+                true => rules.push(old),
+                false => equations.push((old.left, old.right)),
+            }
+        }
+        rules.push(rule);
+        self.trs.rules = rules;
+
+        for i in 0..self.trs.rules.len() {
+            let right = self.normalize(&self.trs.rules[i].right);
+            self.trs.rules[i].right = right;
+        }
+        equations
+    }
+
+    /// Переименовывает переменные правила в порядке вхождения: сначала исходные имена,
+    /// затем они же со штрихами.
+    fn rename(&mut self, rule: Rule) -> Rule {
+        let mut occurrences = Vec::new();
+        for term in [&rule.left, &rule.right] {
+            for variable in ordered_variables(term, &self.trs.variables) {
+                if !occurrences.contains(&variable) {
+                    occurrences.push(variable);
+                }
+            }
+        }
+
+        let names = (0..).flat_map(|primes| {
+            self.variables.iter().map(move |v| format!("{}{}", v, "'".repeat(primes)))
+        });
+        let names: Vec<String> = names
+            .filter(|name| !self.trs.constants.contains(name) && !self.trs.functions.contains_key(name))
+            .take(occurrences.len())
+            .collect();
+        self.trs.variables.extend(names.iter().cloned());
+
+        let substitution: Substitution = occurrences
+            .into_iter()
+            .zip(names)
+            .map(|(variable, name)| (variable, Term::new(&name, Vec::new())))
+            .collect();
+        Rule { left: apply(&rule.left, &substitution), right: apply(&rule.right, &substitution), span: rule.span }
+    }
+
+    fn failure(&self, reason: FailureReason) -> CompletionFailure {
+        CompletionFailure { reason, rules: self.trs.rules.clone() }
+    }
+
+    /// Итоговая TRS; переменные — исходные и встречающиеся в правилах.
+    fn result(self, original: &HashSet<String>) -> ParsedDataTRS {
+        let mut variables = original.clone();
+        for rule in &self.trs.rules {
+            variables.extend(ordered_variables(&rule.left, &self.trs.variables));
+            variables.extend(ordered_variables(&rule.right, &self.trs.variables));
+        }
+        ParsedDataTRS { variables, ..self.trs }
+    }
+}
+
+impl fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FailureReason::Unorientable { left, right } =>
+                write!(f, "Уравнение {} = {} нельзя ориентировать", left, right),
+            FailureReason::Inadmissible { errors } => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "Недопустимые веса KBO: {}", errors.join("; "))
+            }
+            FailureReason::Overflow { message } =>
+                write!(f, "{}", message),
+            FailureReason::StepLimit { steps } =>
+                write!(f, "Пополнение прервано: превышен лимит в {} шагов", steps),
+            FailureReason::TimeLimit =>
+                write!(f, "Пополнение прервано: превышено ограничение по времени"),
+        }
+    }
+}
+
+fn sorted(variables: &HashSet<String>) -> Vec<String> {
+    let mut variables: Vec<String> = variables.iter().cloned().collect();
+    variables.sort();
+    variables
+}
+
+/// Переменные терма в порядке первого вхождения.
+fn ordered_variables(term: &Term, variables: &HashSet<String>) -> Vec<String> {
+    let mut result = Vec::new();
+    for position in term.positions() {
+        let subterm = term.subterm(&position).unwrap();
+        if subterm.is_variable(variables) && !result.contains(&subterm.value) {
+            result.push(subterm.value.clone());
+        }
+    }
+    result
+}
+
+/// Листья терма, не являющиеся константами TRS (в том числе переименованные переменные).
+fn term_variables(term: &Term, trs: &ParsedDataTRS) -> Vec<String> {
+    term.positions()
+        .iter()
+        .map(|position| term.subterm(position).unwrap())
+        .filter(|subterm| subterm.childs.is_empty() && !trs.constants.contains(&subterm.value))
+        .map(|subterm| subterm.value.clone())
+        .collect()
+}
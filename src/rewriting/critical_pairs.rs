@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+use std::fmt;
+use crate::models::ParsedDataTRS;
+use crate::models::data_structures::{serialize_term, Term};
+use super::rewriter::Rewriter;
+use super::substitution::apply;
+use super::unification::{rename, unify};
+
+// Ограничение на число термов, достижимых из одной части критической пары
+const REACHABLE_LIMIT: usize = 10_000;
+
+/// Перекрытие левой части правила `inner_rule` с подтермом левой части `outer_rule`
+/// в позиции `position` (номера аргументов с нуля; пустая — корень).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+pub struct CriticalPair {
+    pub outer_rule: usize,
+    pub inner_rule: usize,
+    pub position: Vec<usize>,
+    #[serde(serialize_with = "serialize_term")]
+    pub peak: Term,
+    /// Результат переписывания пика внешним правилом в корне.
+    #[serde(serialize_with = "serialize_term")]
+    pub left: Term,
+    /// Результат переписывания пика внутренним правилом в позиции `position`.
+    #[serde(serialize_with = "serialize_term")]
+    pub right: Term,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Joinability {
+    Joinable {
+        #[serde(serialize_with = "serialize_term")]
+        common: Term,
+    },
+    /// Все термы, достижимые из обеих частей, перебраны, общих среди них нет.
+    NotJoinable,
+    /// Исчерпано ограничение на глубину перебора.
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct CriticalPairVerdict {
+    #[serde(flatten)]
+    pub pair: CriticalPair,
+    pub joinability: Joinability,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct ConfluenceReport {
+    pub pairs: Vec<CriticalPairVerdict>,
+    /// `None`, если сводимость хотя бы одной пары не установлена, а несводимых пар нет.
+    pub locally_confluent: Option<bool>,
+}
+
+/// Все критические пары TRS, кроме тривиального перекрытия правила с самим собой в корне.
+/// Переменные внутреннего правила переименовываются добавлением штрихов,
+/// чтобы у перекрывающихся правил не было общих переменных.
+pub fn critical_pairs(trs: &ParsedDataTRS) -> Vec<CriticalPair> {
+    let suffix = fresh_suffix(trs);
+    let mut variables = trs.variables.clone();
+    variables.extend(trs.variables.iter().map(|v| format!("{}{}", v, suffix)));
+
+    let mut pairs = Vec::new();
+    for (i, outer) in trs.rules.iter().enumerate() {
+        for position in outer.left.positions() {
+            let subterm = outer.left.subterm(&position).unwrap();
+            if subterm.is_variable(&trs.variables) {
+                continue;
+            }
+            for (j, inner) in trs.rules.iter().enumerate() {
+                if i == j && position.is_empty() {
+                    continue;
+                }
+                let inner_left = rename(&inner.left, &trs.variables, &suffix);
+                let substitution = match unify(subterm, &inner_left, &variables) {
+                    Some(substitution) => substitution,
+                    None => continue,
+                };
+                let inner_right = rename(&inner.right, &trs.variables, &suffix);
+                pairs.push(CriticalPair {
+                    outer_rule: i,
+                    inner_rule: j,
+                    position: position.clone(),
+                    peak: apply(&outer.left, &substitution),
+                    left: apply(&outer.right, &substitution),
+                    right: apply(&outer.left.replace(&position, inner_right), &substitution),
+                });
+            }
+        }
+    }
+    pairs
+}
+
+/// Проверка сводимости `s` и `t`: перебор термов, достижимых не более чем за `max_depth` шагов.
+pub fn joinable(rewriter: &Rewriter, s: &Term, t: &Term, max_depth: usize) -> Joinability {
+    let mut left = Reachable::new(s);
+    let mut right = Reachable::new(t);
+    for _ in 0..max_depth {
+        if let Some(common) = left.common(&right) {
+            return Joinability::Joinable { common };
+        }
+        if left.closed() && right.closed() {
+            return Joinability::NotJoinable;
+        }
+        left.expand(rewriter);
+        right.expand(rewriter);
+    }
+    match left.common(&right) {
+        Some(common) => Joinability::Joinable { common },
+        None if left.closed() && right.closed() => Joinability::NotJoinable,
+        None => Joinability::Unknown,
+    }
+}
+
+/// Локальная конфлюэнтность: сводимость всех критических пар.
+pub fn check_local_confluence(trs: &ParsedDataTRS, max_depth: usize) -> ConfluenceReport {
+    let rewriter = Rewriter::new(trs);
+    let pairs: Vec<CriticalPairVerdict> = critical_pairs(trs)
+        .into_iter()
+        .map(|pair| {
+            let joinability = joinable(&rewriter, &pair.left, &pair.right, max_depth);
+            CriticalPairVerdict { pair, joinability }
+        })
+        .collect();
+
+    let locally_confluent = if pairs.iter().any(|v| v.joinability == Joinability::NotJoinable) {
+        Some(false)
+    } else if pairs.iter().any(|v| v.joinability == Joinability::Unknown) {
+        None
+    } else {
+        Some(true)
+    };
+    ConfluenceReport { pairs, locally_confluent }
+}
+
+struct Reachable {
+    terms: Vec<Term>,
+    seen: HashSet<Term>,
+    frontier: Vec<Term>,
+    overflow: bool,
+}
+
+impl Reachable {
+    fn new(term: &Term) -> Self {
+        Reachable {
+            terms: vec![term.clone()],
+            seen: HashSet::from([term.clone()]),
+            frontier: vec![term.clone()],
+            overflow: false,
+        }
+    }
+
+    fn expand(&mut self, rewriter: &Rewriter) {
+        let mut next = Vec::new();
+        for term in &self.frontier {
+            for reduct in rewriter.reducts(term) {
+                if self.seen.contains(&reduct) {
+                    continue;
+                }
+                if self.seen.len() >= REACHABLE_LIMIT {
+                    self.overflow = true;
+                    break;
+                }
+                self.seen.insert(reduct.clone());
+                self.terms.push(reduct.clone());
+                next.push(reduct);
+            }
+        }
+        self.frontier = next;
+    }
+
+    /// Все достижимые термы найдены.
+    fn closed(&self) -> bool {
+        self.frontier.is_empty() && !self.overflow
+    }
+
+    fn common(&self, other: &Reachable) -> Option<Term> {
+        self.terms.iter().find(|term| other.seen.contains(term)).cloned()
+    }
+}
+
+impl fmt::Display for CriticalPair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let position = match self.position.is_empty() {
+            true => "ε".to_string(),
+            false => self.position.iter().map(|i| (i + 1).to_string()).collect::<Vec<_>>().join("."),
+        };
+        write!(f, "правила {} и {}, позиция {}: {} ← {} → {}",
+               self.outer_rule, self.inner_rule, position, self.left, self.peak, self.right)
+    }
+}
+
+impl fmt::Display for CriticalPairVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.joinability {
+            Joinability::Joinable { common } => write!(f, "{}: сводится к {}", self.pair, common),
+            Joinability::NotJoinable => write!(f, "{}: не сводится", self.pair),
+            Joinability::Unknown => write!(f, "{}: сводимость не установлена", self.pair),
+        }
+    }
+}
+
+/// Кратчайший суффикс из штрихов, не создающий совпадений с символами TRS.
+fn fresh_suffix(trs: &ParsedDataTRS) -> String {
+    let mut suffix = "'".to_string();
+    let taken = |name: &String| {
+        trs.variables.contains(name) || trs.constants.contains(name) || trs.functions.contains_key(name)
+    };
+    while trs.variables.iter().any(|v| taken(&format!("{}{}", v, suffix))) {
+        suffix.push('\'');
+    }
+    suffix
+}
@@ -0,0 +1,11 @@
+pub mod substitution;
+pub mod rewriter;
+pub mod unification;
+pub mod critical_pairs;
+pub mod completion;
+
+pub use substitution::{apply, match_term, Substitution};
+pub use rewriter::{Redex, Reduction, RewriteStep, Rewriter, Strategy};
+pub use unification::{rename, unify};
+pub use critical_pairs::{check_local_confluence, critical_pairs, joinable, ConfluenceReport, CriticalPair, CriticalPairVerdict, Joinability};
+pub use completion::{complete, CompletionFailure, CompletionOptions, FailureReason, ReductionOrder};
@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+use crate::models::ParsedDataTRS;
+use crate::models::data_structures::{Rule, Term};
+use super::substitution::{apply, match_term};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Strategy {
+    LeftmostInnermost,
+    LeftmostOutermost,
+    ParallelOutermost,
+}
+
+/// Применение правила `rule` к подтерму в позиции `position`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+pub struct Redex {
+    pub rule: usize,
+    pub position: Vec<usize>,
+}
+
+/// Шаг переписывания; при параллельной стратегии содержит несколько редексов.
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct RewriteStep {
+    pub redexes: Vec<Redex>,
+    pub term: Term,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct Reduction {
+    pub start: Term,
+    pub steps: Vec<RewriteStep>,
+    /// `false`, если вычисление остановлено по ограничению числа шагов.
+    pub normal_form: bool,
+}
+
+impl Reduction {
+    pub fn result(&self) -> &Term {
+        self.steps.last().map_or(&self.start, |step| &step.term)
+    }
+}
+
+#[derive(Debug)]
+pub struct Rewriter<'a> {
+    rules: &'a [Rule],
+    variables: &'a HashSet<String>,
+}
+
+impl<'a> Rewriter<'a> {
+    pub fn new(trs: &'a ParsedDataTRS) -> Self {
+        Rewriter::from_rules(&trs.rules, &trs.variables)
+    }
+
+    pub fn from_rules(rules: &'a [Rule], variables: &'a HashSet<String>) -> Self {
+        Rewriter { rules, variables }
+    }
+
+    /// Переписывает подтерм в позиции `position` по правилу `rule`;
+    /// `None`, если позиции нет или левая часть правила не сопоставляется.
+    pub fn rewrite_at(&self, term: &Term, position: &[usize], rule: usize) -> Option<Term> {
+        let rule = self.rules.get(rule)?;
+        let subterm = term.subterm(position)?;
+        let substitution = match_term(&rule.left, subterm, self.variables)?;
+        Some(term.replace(position, apply(&rule.right, &substitution)))
+    }
+
+    /// Первое по порядку правило, применимое к корню терма.
+    pub fn matching_rule(&self, term: &Term) -> Option<usize> {
+        self.rules
+            .iter()
+            .position(|rule| match_term(&rule.left, term, self.variables).is_some())
+    }
+
+    pub fn is_normal_form(&self, term: &Term) -> bool {
+        term.positions()
+            .iter()
+            .all(|p| self.matching_rule(term.subterm(p).unwrap()).is_none())
+    }
+
+    /// Все редексы терма в порядке обхода сверху вниз, слева направо.
+    pub fn redexes(&self, term: &Term) -> Vec<Redex> {
+        term.positions()
+            .into_iter()
+            .filter_map(|position| {
+                let rule = self.matching_rule(term.subterm(&position).unwrap())?;
+                Some(Redex { rule, position })
+            })
+            .collect()
+    }
+
+    /// Все термы, получаемые одним шагом переписывания (любым правилом в любой позиции).
+    pub fn reducts(&self, term: &Term) -> Vec<Term> {
+        let mut reducts = Vec::new();
+        for position in term.positions() {
+            for rule in 0..self.rules.len() {
+                if let Some(reduct) = self.rewrite_at(term, &position, rule) {
+                    if !reducts.contains(&reduct) {
+                        reducts.push(reduct);
+                    }
+                }
+            }
+        }
+        reducts
+    }
+
+    /// Один шаг по стратегии; `None`, если терм в нормальной форме.
+    pub fn step(&self, term: &Term, strategy: Strategy) -> Option<RewriteStep> {
+        let redexes = match strategy {
+            Strategy::LeftmostInnermost => vec![self.innermost_redex(term, Vec::new())?],
+            Strategy::LeftmostOutermost => vec![self.outermost_redexes(term, Vec::new(), true).pop()?],
+            Strategy::ParallelOutermost => self.outermost_redexes(term, Vec::new(), false),
+        };
+        if redexes.is_empty() {
+            return None;
+        }
+
+        let mut result = term.clone();
+        for redex in &redexes {
+            // Внешние редексы не пересекаются, поэтому порядок применения не важен
+            result = self.rewrite_at(&result, &redex.position, redex.rule)?;
+        }
+        Some(RewriteStep { redexes, term: result })
+    }
+
+    /// Переписывание до нормальной формы, но не более `max_steps` шагов.
+    pub fn normalize(&self, term: &Term, strategy: Strategy, max_steps: usize) -> Reduction {
+        let mut reduction = Reduction { start: term.clone(), steps: Vec::new(), normal_form: false };
+        let mut current = term.clone();
+        while reduction.steps.len() < max_steps {
+            match self.step(&current, strategy) {
+                Some(step) => {
+                    current = step.term.clone();
+                    reduction.steps.push(step);
+                }
+                None => {
+                    reduction.normal_form = true;
+                    return reduction;
+                }
+            }
+        }
+        reduction.normal_form = self.is_normal_form(&current);
+        reduction
+    }
+
+    fn innermost_redex(&self, term: &Term, position: Vec<usize>) -> Option<Redex> {
+        for (i, child) in term.childs.iter().enumerate() {
+            let mut child_position = position.clone();
+            child_position.push(i);
+            if let Some(redex) = self.innermost_redex(child, child_position) {
+                return Some(redex);
+            }
+        }
+        let rule = self.matching_rule(term)?;
+        Some(Redex { rule, position })
+    }
+
+    // При `first_only` поиск останавливается на первом (самом левом) внешнем редексе
+    fn outermost_redexes(&self, term: &Term, position: Vec<usize>, first_only: bool) -> Vec<Redex> {
+        if let Some(rule) = self.matching_rule(term) {
+            return vec![Redex { rule, position }];
+        }
+        let mut redexes = Vec::new();
+        for (i, child) in term.childs.iter().enumerate() {
+            let mut child_position = position.clone();
+            child_position.push(i);
+            redexes.extend(self.outermost_redexes(child, child_position, first_only));
+            if first_only && !redexes.is_empty() {
+                break;
+            }
+        }
+        redexes
+    }
+}
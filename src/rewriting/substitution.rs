@@ -0,0 +1,48 @@
+use std::collections::{HashMap, HashSet};
+use crate::models::data_structures::Term;
+
+/// Подстановка: переменная -> терм.
+pub type Substitution = HashMap<String, Term>;
+
+/// Применяет подстановку ко всем вхождениям переменных терма.
+pub fn apply(term: &Term, substitution: &Substitution) -> Term {
+    if term.childs.is_empty() {
+        if let Some(value) = substitution.get(&term.value) {
+            return value.clone();
+        }
+        return term.clone();
+    }
+    let mut result = term.clone();
+    result.childs = term.childs.iter().map(|c| apply(c, substitution)).collect();
+    result
+}
+
+/// Сопоставление с образцом: подстановка σ, такая что `σ(pattern) = term`.
+/// Переменными считаются только символы из `variables`, встречающиеся в `pattern`.
+pub fn match_term(pattern: &Term, term: &Term, variables: &HashSet<String>) -> Option<Substitution> {
+    let mut substitution = Substitution::new();
+    if match_into(pattern, term, variables, &mut substitution) {
+        Some(substitution)
+    } else {
+        None
+    }
+}
+
+fn match_into(pattern: &Term, term: &Term, variables: &HashSet<String>, substitution: &mut Substitution) -> bool {
+    if pattern.is_variable(variables) {
+        return match substitution.get(&pattern.value) {
+            Some(bound) => bound == term,
+            None => {
+                substitution.insert(pattern.value.clone(), term.clone());
+                true
+            }
+        };
+    }
+
+    pattern.value == term.value
+        && pattern.childs.len() == term.childs.len()
+        && pattern.childs
+            .iter()
+            .zip(&term.childs)
+            .all(|(p, t)| match_into(p, t, variables, substitution))
+}
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+use crate::models::data_structures::Term;
+use super::substitution::{apply, Substitution};
+
+/// Наиболее общий унификатор термов `s` и `t` (с проверкой вхождения).
+/// Переменными считаются символы из `variables`; результат идемпотентен:
+/// переменные из его области определения не входят в подставляемые термы.
+pub fn unify(s: &Term, t: &Term, variables: &HashSet<String>) -> Option<Substitution> {
+    let mut substitution = Substitution::new();
+    let mut equations = vec![(s.clone(), t.clone())];
+    while let Some((s, t)) = equations.pop() {
+        let s = apply(&s, &substitution);
+        let t = apply(&t, &substitution);
+        if s == t {
+            continue;
+        }
+        let (variable, term) = match (s.is_variable(variables), t.is_variable(variables)) {
+            (true, _) => (s.value, t),
+            (false, true) => (t.value, s),
+            (false, false) => {
+                if s.value != t.value || s.childs.len() != t.childs.len() {
+                    return None;
+                }
+                equations.extend(s.childs.into_iter().zip(t.childs));
+                continue;
+            }
+        };
+        if occurs(&variable, &term, variables) {
+            return None;
+        }
+        let binding = Substitution::from([(variable.clone(), term.clone())]);
+        for value in substitution.values_mut() {
+            *value = apply(value, &binding);
+        }
+        substitution.insert(variable, term);
+    }
+    Some(substitution)
+}
+
+/// Переименовывает переменные терма, добавляя к их именам `suffix`.
+pub fn rename(term: &Term, variables: &HashSet<String>, suffix: &str) -> Term {
+    if term.is_variable(variables) {
+        let mut renamed = term.clone();
+        renamed.value = format!("{}{}", term.value, suffix);
+        return renamed;
+    }
+    let mut result = term.clone();
+    result.childs = term.childs.iter().map(|child| rename(child, variables, suffix)).collect();
+    result
+}
+
+fn occurs(variable: &str, term: &Term, variables: &HashSet<String>) -> bool {
+    match term.is_variable(variables) {
+        true => term.value == variable,
+        false => term.childs.iter().any(|child| occurs(variable, child, variables)),
+    }
+}
@@ -1,78 +1,207 @@
 use rouille::{try_or_400};
 use crate::models::data_structures::{Model, Rule};
-use crate::models::{ParsedDataInterpret};
-use crate::models::ParsedData::{Interpret, TRS};
-use crate::parsers::{Parse, ParserTRS, ParserInterpret};
+use crate::models::{ParseError, ParsedDataArctic, ParsedDataInterpret, ParsedDataMatrix, ParsedDataTRS};
+use crate::models::ParsedData::{Arctic, Interpret, Matrix, TRS};
+use crate::parsers::{Parse, ParserTRS, ParserInterpret, ParserMatrix, ParserArctic, TrsFormat};
+use crate::termination::{self, PolynomialOrder, RuleVerdict, SearchOptions, Template};
 
 #[derive(Debug)]
 #[derive(serde::Deserialize)]
 struct InputJson {
-    Interpretation: String,
-    TRS: String,
+    #[serde(rename = "Interpretation")]
+    interpretation: String,
+    #[serde(rename = "TRS")]
+    trs: String,
+    #[serde(rename = "Format", default)]
+    format: TrsFormat,
+    #[serde(rename = "Minimum", default = "default_minimum")]
+    minimum: i64,
+    #[serde(rename = "Warnings", default)]
+    warnings: bool,
+}
+
+fn default_minimum() -> i64 {
+    termination::DEFAULT_MINIMUM
+}
+
+#[derive(Debug)]
+#[derive(serde::Deserialize)]
+struct SearchInputJson {
+    #[serde(rename = "TRS")]
+    trs: String,
+    #[serde(rename = "Format", default)]
+    format: TrsFormat,
+    #[serde(rename = "MaxCoefficient")]
+    max_coefficient: Option<i64>,
+    #[serde(rename = "Template", default)]
+    template: Template,
 }
 
 #[derive(serde::Serialize)]
-struct ErrorJson {
+struct SearchResponseJson {
+    found: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    json_interpret: Option<Functions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interpretation: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ErrorJson {
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    error_trs: Vec<String>,
+    pub error_trs: Vec<ParseError>,
 
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    error_interpretation: Vec<String>,
+    pub error_interpretation: Vec<ParseError>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub error_termination: Vec<String>,
 }
 
 #[derive(serde::Serialize)]
-struct ResponseJson {
-    pub json_TRS: Vec<Rule>,
-    pub json_interpret: functions,
+pub struct ResponseJson {
+    #[serde(rename = "json_TRS")]
+    pub json_trs: Vec<Rule>,
+    /// Предупреждения о правилах TRS (повторы, совпадающие части), если они запрошены.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub error_trs: Vec<ParseError>,
+    pub json_interpret: Functions,
+    pub termination: Vec<RuleVerdict>,
+    /// Нарушения монотонности и выхода за носитель в интерпретации (не мешают проверке правил).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<ParseError>,
 }
 
 #[derive(serde::Serialize)]
-struct functions {
+pub struct Functions {
     pub functions: ParsedDataInterpret
 }
 
 pub fn handle_request(request: &rouille::Request) -> rouille::Response {
     let json: InputJson = try_or_400!(rouille::input::json_input(request));
-    let mut err = ErrorJson { error_trs: Vec::new(), error_interpretation: Vec::new() };
-    let mut res = ResponseJson { json_TRS: Vec::new(), json_interpret: functions{ functions: vec![] } };
-
-    let mut parser_trs = ParserTRS::new(&json.TRS[..]);
-    match parser_trs.parse() {
-        Ok(result) => {
-            println!("Парсинг TRS: {:?}", result);
-            res.json_TRS = match result {
-                TRS(trs) => {
-                    trs.rules.clone()
-                }
-                _ => Vec::new()
-            };
+    match process(&json.trs, json.format, &json.interpretation, json.minimum, json.warnings) {
+        Ok(res) => {
+            println!("Парсинг TRS: {:?}", res.json_trs);
+            println!("Парсинг Interpet: {:?}", res.json_interpret.functions);
+            rouille::Response::json(&res)
+        }
+        Err(err) => rouille::Response::json(&err).with_status_code(400),
+    }
+}
+
+/// Поиск интерпретации с ограниченными коэффициентами для TRS из запроса.
+pub fn handle_search(request: &rouille::Request) -> rouille::Response {
+    let json: SearchInputJson = try_or_400!(rouille::input::json_input(request));
+    let mut err = ErrorJson { error_trs: Vec::new(), error_interpretation: Vec::new(), error_termination: Vec::new() };
+
+    let (trs, _) = match parse_trs(&json.trs, json.format) {
+        Ok(parsed) => parsed,
+        Err(mut e) => {
+            err.error_trs.append(&mut e);
+            return rouille::Response::json(&err).with_status_code(400);
+        }
+    };
+
+    let mut options = SearchOptions { template: json.template, ..SearchOptions::default() };
+    if let Some(max_coefficient) = json.max_coefficient {
+        options.max_coefficient = max_coefficient;
+    }
+    match termination::find_interpretation(&trs, &options) {
+        Ok(Some(interpret)) => rouille::Response::json(&SearchResponseJson {
+            found: true,
+            interpretation: Some(termination::interpretation_to_string(&interpret)),
+            json_interpret: Some(Functions { functions: interpret }),
+        }),
+        Ok(None) => rouille::Response::json(&SearchResponseJson { found: false, json_interpret: None, interpretation: None }),
+        Err(e) => {
+            err.error_termination.push(e);
+            rouille::Response::json(&err).with_status_code(400)
+        }
+    }
+}
+
+/// Разбирает TRS, затем интерпретацию и проверяет завершаемость; `minimum` — наименьшее
+/// значение носителя для проверки монотонности и поиска контрпримеров, `warnings` включает
+/// предупреждения о правилах.
+/// Используется как HTTP-обработчиком, так и консольным режимом.
+pub fn process(trs: &str, format: TrsFormat, interpretation: &str, minimum: i64, warnings: bool) -> Result<ResponseJson, ErrorJson> {
+    let mut err = ErrorJson { error_trs: Vec::new(), error_interpretation: Vec::new(), error_termination: Vec::new() };
+    let mut res = ResponseJson {
+        json_trs: Vec::new(),
+        error_trs: Vec::new(),
+        json_interpret: Functions { functions: vec![] },
+        termination: Vec::new(),
+        diagnostics: Vec::new(),
+    };
+
+    let (parsed_trs, model) = match parse_trs(trs, format) {
+        Ok(parsed) => parsed,
+        Err(mut e) => {
+            err.error_trs.append(&mut e);
+            return Err(err);
+        }
+    };
+    res.json_trs = parsed_trs.rules.clone();
+    if warnings {
+        // Фатальные нарушения уже отсеяны при разборе, остаются предупреждения
+        res.error_trs = parsed_trs.check_well_formed(true);
+        err.error_trs = res.error_trs.clone();
+    }
+
+    match parse_interpretation(interpretation, model) {
+        Ok(interpret) => res.json_interpret = Functions { functions: interpret },
+        Err(mut e) => {
+            err.error_interpretation.append(&mut e);
+            return Err(err);
         }
-        Err(mut e) => err.error_trs.append(&mut e),
     };
+    res.diagnostics = termination::check_interpretation(&res.json_interpret.functions, minimum);
 
+    match PolynomialOrder::new(&res.json_interpret.functions).with_minimum(minimum).check(&parsed_trs) {
+        Ok(verdicts) => res.termination = verdicts,
+        Err(e) => {
+            err.error_termination.push(e);
+            return Err(err);
+        }
+    }
+    Ok(res)
+}
+
+/// Разбирает TRS и возвращает её вместе с моделью сигнатуры для разбора интерпретации.
+pub fn parse_trs(input: &str, format: TrsFormat) -> Result<(ParsedDataTRS, Model), Vec<ParseError>> {
+    let mut parser_trs = ParserTRS::with_format(input, format);
+    let parsed = match parser_trs.parse()? {
+        TRS(trs) => trs,
+        _ => ParsedDataTRS::default(),
+    };
     let model = Model {
         variables: parser_trs.variables,
         constants: parser_trs.constants,
         functions: parser_trs.functions,
     };
-    if  err.error_trs.len() > 0{
-        return rouille::Response::json(&err).with_status_code(400);
+    Ok((parsed, model))
+}
+
+pub fn parse_interpretation(input: &str, model: Model) -> Result<ParsedDataInterpret, Vec<ParseError>> {
+    let mut parser_interpret = ParserInterpret::new(input, model);
+    match parser_interpret.parse()? {
+        Interpret(interpret) => Ok(interpret),
+        _ => Ok(ParsedDataInterpret::default()),
     }
+}
 
-    let mut parser_interpret = ParserInterpret::new(&json.Interpretation[..], model);
-    match parser_interpret.parse() {
-        Ok(result) => {
-            println!("Парсинг Interpet: {:?}", result);
-            res.json_interpret = match result {
-                Interpret(interpret) => functions{functions: interpret},
-                _ => functions{functions: ParsedDataInterpret::default()}
-            };
-        }
-        Err(mut e) => err.error_interpretation.append(&mut e),
-    };
-    if err.error_trs.len() > 0 || err.error_interpretation.len() > 0 {
-        return rouille::Response::json(&err).with_status_code(400);
+pub fn parse_matrix_interpretation(input: &str, model: Model) -> Result<ParsedDataMatrix, Vec<ParseError>> {
+    let mut parser_matrix = ParserMatrix::new(input, model);
+    match parser_matrix.parse()? {
+        Matrix(matrix) => Ok(matrix),
+        _ => Ok(ParsedDataMatrix::default()),
     }
-    let resp = rouille::Response::json(&res);
-    resp
-}
\ No newline at end of file
+}
+
+pub fn parse_arctic_interpretation(input: &str, model: Model) -> Result<ParsedDataArctic, Vec<ParseError>> {
+    let mut parser_arctic = ParserArctic::new(input, model);
+    match parser_arctic.parse()? {
+        Arctic(arctic) => Ok(arctic),
+        _ => Ok(ParsedDataArctic::default()),
+    }
+}
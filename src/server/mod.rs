@@ -2,7 +2,7 @@ pub mod handlers;
 mod page;
 use std::io;
 use rouille::{router};
-use handlers::handle_request;
+use handlers::{handle_request, handle_search};
 use page::handle_page;
 
 pub fn start_server() {
@@ -15,6 +15,9 @@ pub fn start_server() {
                 (POST) (/parse) => {
                     handle_request(request)
                 },
+                (POST) (/search) => {
+                    handle_search(request)
+                },
                 (GET) (/) => {
                     handle_page(request)
                 },
@@ -1,7 +1,7 @@
 use rouille::Response;
 
-pub fn handle_page(request: &rouille::Request) -> rouille::Response {
-    return Response::html(r#"
+pub fn handle_page(_request: &rouille::Request) -> rouille::Response {
+    Response::html(r#"
                         <!DOCTYPE html>
 <html lang="en">
 <head>
@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use crate::models::{ArcticPolynomial, ParsedDataArctic, ParsedDataTRS};
+use crate::models::data_structures::{ArcticInterpretFunction, Rule, Term};
+
+// Значения переменных, перебираемые при поиске контрпримера
+const COUNTEREXAMPLE_BOUND: i64 = 3;
+const COUNTEREXAMPLE_LIMIT: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq)]
+#[derive(serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ArcticVerdict {
+    /// Каждое слагаемое `[r]` меньше некоторого слагаемого `[l]` при любых значениях переменных.
+    Strict,
+    /// Каждое слагаемое `[r]` не больше некоторого слагаемого `[l]`.
+    Weak,
+    /// Поэлементное сравнение не сработало, но контрпример не найден.
+    NotOriented,
+    /// Набор значений переменных, при котором `[l] < [r]`; `None` — `-inf`.
+    Counterexample {
+        assignment: BTreeMap<String, i64>,
+        left: Option<i64>,
+        right: Option<i64>,
+    },
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct ArcticRuleVerdict {
+    pub rule: usize,
+    pub left: ArcticPolynomial,
+    pub right: ArcticPolynomial,
+    pub verdict: ArcticVerdict,
+}
+
+/// Проверка ориентации правил по интерпретации в арктическом полукольце;
+/// переменные принимают натуральные значения.
+#[derive(Debug)]
+pub struct ArcticOrder<'a> {
+    interpretations: HashMap<String, &'a ArcticInterpretFunction>,
+}
+
+impl<'a> ArcticOrder<'a> {
+    pub fn new(interpretation: &'a ParsedDataArctic) -> Self {
+        ArcticOrder {
+            interpretations: interpretation
+                .iter()
+                .map(|function| (function.name.clone(), function))
+                .collect(),
+        }
+    }
+
+    pub fn interpret(&self, term: &Term, variables: &HashSet<String>) -> Result<ArcticPolynomial, String> {
+        if term.is_variable(variables) {
+            return Ok(ArcticPolynomial::variable(&term.value));
+        }
+
+        let function = match self.interpretations.get(&term.value) {
+            Some(function) => function,
+            None => return Err(format!("Для символа {} не задана интерпретация", term.value)),
+        };
+        if function.variables.len() != term.childs.len() {
+            return Err(format!("Не совпадает арность символа {} в TRS и в интерпретации", term.value));
+        }
+
+        let mut substitution = HashMap::new();
+        for (variable, child) in function.variables.iter().zip(&term.childs) {
+            substitution.insert(variable.clone(), self.interpret(child, variables)?);
+        }
+        function.polynomial
+            .compose(&substitution)
+            .ok_or_else(|| format!("Переполнение при вычислении интерпретации символа {}", term.value))
+    }
+
+    pub fn check_rule(&self, index: usize, rule: &Rule, variables: &HashSet<String>) -> Result<ArcticRuleVerdict, String> {
+        let left = self.interpret(&rule.left, variables)?;
+        let right = self.interpret(&rule.right, variables)?;
+
+        let covered = |strict: bool| {
+            right.monomials.iter().all(|r| {
+                left.monomials.iter().any(|l| l.dominates(r) && (!strict || l.coefficient > r.coefficient))
+            })
+        };
+        let verdict = if covered(true) {
+            ArcticVerdict::Strict
+        } else if covered(false) {
+            ArcticVerdict::Weak
+        } else {
+            find_counterexample(&left, &right).unwrap_or(ArcticVerdict::NotOriented)
+        };
+        Ok(ArcticRuleVerdict { rule: index, left, right, verdict })
+    }
+
+    pub fn check(&self, trs: &ParsedDataTRS) -> Result<Vec<ArcticRuleVerdict>, String> {
+        trs.rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| self.check_rule(i, rule, &trs.variables))
+            .collect()
+    }
+
+    /// Символы, интерпретация которых может быть отрицательной или `-inf`
+    /// (нет слагаемого с неотрицательным коэффициентом): для них значения термов
+    /// выходят за натуральные числа и сравнение слагаемых теряет смысл.
+    pub fn ill_defined(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self.interpretations
+            .values()
+            .filter(|function| function.polynomial.monomials.iter().all(|m| m.coefficient < 0))
+            .map(|function| function.name.clone())
+            .collect();
+        symbols.sort();
+        symbols
+    }
+}
+
+fn find_counterexample(left: &ArcticPolynomial, right: &ArcticPolynomial) -> Option<ArcticVerdict> {
+    let variables: BTreeSet<&String> = left.monomials
+        .iter()
+        .chain(&right.monomials)
+        .flat_map(|monomial| monomial.variables.keys())
+        .collect();
+    let mut assignment: HashMap<String, i64> = variables.iter().map(|v| ((*v).clone(), 0)).collect();
+
+    for _ in 0..COUNTEREXAMPLE_LIMIT {
+        if let (Some(l), Some(r)) = (left.evaluate(&assignment), right.evaluate(&assignment)) {
+            // None < Some(_): -inf меньше любого числа
+            if l < r {
+                return Some(ArcticVerdict::Counterexample {
+                    assignment: assignment.into_iter().collect(),
+                    left: l,
+                    right: r,
+                });
+            }
+        }
+
+        // Следующий набор значений в порядке счётчика с основанием COUNTEREXAMPLE_BOUND + 1
+        let mut carried = true;
+        for variable in &variables {
+            let value = assignment.get_mut(*variable).unwrap();
+            if *value < COUNTEREXAMPLE_BOUND {
+                *value += 1;
+                carried = false;
+                break;
+            }
+            *value = 0;
+        }
+        if carried {
+            break;
+        }
+    }
+    None
+}
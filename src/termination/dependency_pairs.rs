@@ -0,0 +1,256 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::models::ParsedDataTRS;
+use crate::models::data_structures::{serialize_term, Rule, Term};
+use crate::rewriting::unify;
+
+/// Зависимая пара `l# -> t#`: `t` — подтерм правой части правила `rule` в позиции `position`
+/// с определённым корневым символом, не являющийся собственным подтермом `l`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+pub struct DependencyPair {
+    pub rule: usize,
+    pub position: Vec<usize>,
+    #[serde(serialize_with = "serialize_term")]
+    pub left: Term,
+    #[serde(serialize_with = "serialize_term")]
+    pub right: Term,
+}
+
+/// Оценка графа зависимостей: ребро `i -> j`, если `ren(cap(right_i))` унифицируется с `left_j`.
+/// `sccs` — компоненты сильной связности, содержащие хотя бы один цикл,
+/// в обратном топологическом порядке.
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct DependencyGraph {
+    /// Определённый символ -> помеченный символ.
+    pub marked: BTreeMap<String, String>,
+    pub pairs: Vec<DependencyPair>,
+    pub edges: Vec<(usize, usize)>,
+    pub sccs: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    pub fn new(trs: &ParsedDataTRS) -> Self {
+        let defined: HashSet<&String> = trs.rules.iter().map(|rule| &rule.left.value).collect();
+        let marked = marked_symbols(trs, &defined);
+
+        let mut pairs = Vec::new();
+        for (i, rule) in trs.rules.iter().enumerate() {
+            for position in rule.right.positions() {
+                let subterm = rule.right.subterm(&position).unwrap();
+                if !defined.contains(&subterm.value) || is_proper_subterm(subterm, &rule.left) {
+                    continue;
+                }
+                pairs.push(DependencyPair {
+                    rule: i,
+                    position,
+                    left: mark(&rule.left, &marked),
+                    right: mark(subterm, &marked),
+                });
+            }
+        }
+
+        let mut edges = Vec::new();
+        for (i, from) in pairs.iter().enumerate() {
+            let mut fresh = Fresh::new(trs);
+            let capped = fresh.cap(&from.right, &defined);
+            let mut variables = trs.variables.clone();
+            variables.extend(fresh.names);
+            for (j, to) in pairs.iter().enumerate() {
+                if unify(&capped, &to.left, &variables).is_some() {
+                    edges.push((i, j));
+                }
+            }
+        }
+
+        let sccs = strongly_connected_components(pairs.len(), &edges)
+            .into_iter()
+            .filter(|scc| scc.len() > 1 || edges.contains(&(scc[0], scc[0])))
+            .collect();
+        DependencyGraph { marked, pairs, edges, sccs }
+    }
+
+    /// TRS из зависимых пар компоненты `scc`; помеченные символы добавлены в сигнатуру.
+    /// Её можно передать в `PolynomialOrder` вместе с интерпретацией помеченных символов.
+    pub fn scc_trs(&self, trs: &ParsedDataTRS, scc: usize) -> ParsedDataTRS {
+        let mut functions = trs.functions.clone();
+        let mut constants = trs.constants.clone();
+        for (symbol, marked) in &self.marked {
+            match trs.functions.get(symbol) {
+                Some(arity) => { functions.insert(marked.clone(), *arity); }
+                None => { constants.insert(marked.clone()); }
+            }
+        }
+        let rules = self.sccs[scc]
+            .iter()
+            .map(|&i| Rule {
+                left: self.pairs[i].left.clone(),
+                right: self.pairs[i].right.clone(),
+                span: trs.rules[self.pairs[i].rule].span,
+            })
+            .collect();
+        ParsedDataTRS { rules, variables: trs.variables.clone(), constants, functions }
+    }
+
+    /// Граф в формате Graphviz DOT; компоненты сильной связности выделены кластерами.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dependency_graph {\n");
+        for (i, pair) in self.pairs.iter().enumerate() {
+            dot.push_str(&format!("  p{} [label=\"{}: {} -> {}\"];\n", i, i, escape(&pair.left), escape(&pair.right)));
+        }
+        for (i, j) in &self.edges {
+            dot.push_str(&format!("  p{} -> p{};\n", i, j));
+        }
+        for (k, scc) in self.sccs.iter().enumerate() {
+            let nodes: Vec<String> = scc.iter().map(|i| format!("p{};", i)).collect();
+            dot.push_str(&format!("  subgraph cluster_{} {{ label=\"SCC {}\"; {} }}\n", k, k, nodes.join(" ")));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Помеченный символ — имя в верхнем регистре (`add` -> `ADD`);
+/// при совпадении с уже занятым именем добавляются штрихи.
+fn marked_symbols(trs: &ParsedDataTRS, defined: &HashSet<&String>) -> BTreeMap<String, String> {
+    let mut taken: HashSet<String> = trs.variables
+        .iter()
+        .chain(&trs.constants)
+        .chain(trs.functions.keys())
+        .cloned()
+        .collect();
+    let mut defined: Vec<&&String> = defined.iter().collect();
+    defined.sort();
+
+    let mut marked = BTreeMap::new();
+    for symbol in defined {
+        let mut name = symbol.to_uppercase();
+        while taken.contains(&name) {
+            name.push('\'');
+        }
+        taken.insert(name.clone());
+        marked.insert(symbol.to_string(), name);
+    }
+    marked
+}
+
+fn mark(term: &Term, marked: &BTreeMap<String, String>) -> Term {
+    let mut result = term.clone();
+    result.value = marked[&term.value].clone();
+    result
+}
+
+fn is_proper_subterm(subterm: &Term, term: &Term) -> bool {
+    term.childs.iter().any(|child| child == subterm || is_proper_subterm(subterm, child))
+}
+
+/// Генератор свежих переменных для `cap` и `ren`.
+struct Fresh<'a> {
+    trs: &'a ParsedDataTRS,
+    names: Vec<String>,
+    counter: usize,
+}
+
+impl<'a> Fresh<'a> {
+    fn new(trs: &'a ParsedDataTRS) -> Self {
+        Fresh { trs, names: Vec::new(), counter: 0 }
+    }
+
+    fn next(&mut self) -> Term {
+        loop {
+            self.counter += 1;
+            let name = format!("?{}", self.counter);
+            let taken = self.trs.variables.contains(&name)
+                || self.trs.constants.contains(&name)
+                || self.trs.functions.contains_key(&name);
+            if !taken {
+                self.names.push(name.clone());
+                return Term::new(&name, Vec::new());
+            }
+        }
+    }
+
+    /// `ren(cap(t))` для помеченного терма: аргументы с определённым корнем
+    /// и все вхождения переменных заменяются различными свежими переменными.
+    fn cap(&mut self, term: &Term, defined: &HashSet<&String>) -> Term {
+        let mut result = term.clone();
+        result.childs = term.childs.iter().map(|child| self.cap_argument(child, defined)).collect();
+        result
+    }
+
+    fn cap_argument(&mut self, term: &Term, defined: &HashSet<&String>) -> Term {
+        let is_variable = term.is_variable(&self.trs.variables);
+        if is_variable || defined.contains(&term.value) {
+            return self.next();
+        }
+        self.cap(term, defined)
+    }
+}
+
+/// Алгоритм Тарьяна; вершины `0..count`.
+fn strongly_connected_components(count: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut successors: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, j) in edges {
+        successors.entry(*i).or_default().push(*j);
+    }
+
+    struct State {
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next: usize,
+        sccs: Vec<Vec<usize>>,
+    }
+
+    fn visit(v: usize, successors: &HashMap<usize, Vec<usize>>, state: &mut State) {
+        state.index[v] = Some(state.next);
+        state.low[v] = state.next;
+        state.next += 1;
+        state.stack.push(v);
+        state.on_stack[v] = true;
+
+        for &w in successors.get(&v).into_iter().flatten() {
+            match state.index[w] {
+                None => {
+                    visit(w, successors, state);
+                    state.low[v] = state.low[v].min(state.low[w]);
+                }
+                Some(index) if state.on_stack[w] => state.low[v] = state.low[v].min(index),
+                Some(_) => (),
+            }
+        }
+
+        if Some(state.low[v]) == state.index[v] {
+            let mut scc = Vec::new();
+            while let Some(w) = state.stack.pop() {
+                state.on_stack[w] = false;
+                scc.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            scc.sort();
+            state.sccs.push(scc);
+        }
+    }
+
+    let mut state = State {
+        index: vec![None; count],
+        low: vec![0; count],
+        on_stack: vec![false; count],
+        stack: Vec::new(),
+        next: 0,
+        sccs: Vec::new(),
+    };
+    for v in 0..count {
+        if state.index[v].is_none() {
+            visit(v, &successors, &mut state);
+        }
+    }
+    state.sccs
+}
+
+fn escape(term: &Term) -> String {
+    term.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+}
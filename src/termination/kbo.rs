@@ -0,0 +1,304 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use crate::models::{ParseError, ParseErrorKind, ParsedDataTRS, Parser};
+use crate::models::data_structures::Term;
+use super::precedence::Precedence;
+
+/// Нарушение условий допустимости весов KBO.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AdmissibilityError {
+    MissingWeight { symbol: String },
+    ZeroVariableWeight,
+    /// Вес константы меньше веса переменной.
+    LightConstant { symbol: String, weight: u64 },
+    /// Унарный символ нулевого веса не больше перечисленных символов в прецедентности.
+    ZeroWeightUnary { symbol: String, not_greater_than: Vec<String> },
+}
+
+/// Случай определения KBO, по которому `l >kbo r`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+#[serde(tag = "case", rename_all = "snake_case")]
+pub enum KboCase {
+    /// Вес левой части больше.
+    Weight,
+    /// Веса равны, левая часть — `f(f(...f(x)))` для унарного `f` нулевого веса, правая — `x`.
+    UnaryTower { symbol: String },
+    /// Веса равны, корневой символ левой части больше в прецедентности.
+    Precedence { greater: String, smaller: String },
+    /// Веса и корневые символы равны, первое различие аргументов — в `argument` (с нуля).
+    Lexicographic { argument: usize },
+}
+
+/// Переменная, входящая в правую часть чаще, чем в левую.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+pub struct VariableCount {
+    pub variable: String,
+    pub left: usize,
+    pub right: usize,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct KboVerdict {
+    pub rule: usize,
+    pub oriented: bool,
+    pub left_weight: u64,
+    pub right_weight: u64,
+    pub variable_failures: Vec<VariableCount>,
+    pub case: Option<KboCase>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct KboReport {
+    pub admissibility: Vec<AdmissibilityError>,
+    pub rules: Vec<KboVerdict>,
+}
+
+/// Порядок Кнута–Бендикса: веса символов, вес переменных и прецедентность.
+#[derive(Debug, Clone)]
+pub struct Kbo {
+    pub weights: BTreeMap<String, u64>,
+    pub variable_weight: u64,
+    pub precedence: Precedence,
+}
+
+#[derive(serde::Deserialize)]
+struct KboJson {
+    weights: BTreeMap<String, u64>,
+    #[serde(default = "default_variable_weight")]
+    variable_weight: u64,
+    #[serde(default)]
+    precedence: String,
+}
+
+fn default_variable_weight() -> u64 {
+    1
+}
+
+impl Kbo {
+    pub fn new(weights: BTreeMap<String, u64>, variable_weight: u64, precedence: Precedence) -> Self {
+        Kbo { weights, variable_weight, precedence }
+    }
+
+    /// Разбор описания вида
+    /// ```text
+    /// weights = f: 1, g: 0, a: 1
+    /// variable_weight = 1
+    /// precedence = g > f > a
+    /// ```
+    /// Строка `variable_weight` необязательна (по умолчанию 1).
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(input);
+        let mut kbo = Kbo::new(BTreeMap::new(), default_variable_weight(), Precedence::new());
+        loop {
+            parser.skip_whitespace();
+            if parser.peek().is_err() {
+                break;
+            }
+            let expected = "weights, variable_weight или precedence";
+            let section = parser.read_identifier(expected)?;
+            let span = parser.previous_span();
+            parser.read_exact_char('=')?;
+            match section.as_str() {
+                "weights" => kbo.parse_weights(&mut parser)?,
+                "variable_weight" => kbo.variable_weight = parse_weight(&mut parser)?,
+                "precedence" => kbo.precedence.parse_line(&mut parser)?,
+                _ => return Err(ParseError::new(ParseErrorKind::UnknownSection, span)
+                    .found(section)
+                    .expected(expected)),
+            }
+            parser.read_eol()?;
+        }
+        Ok(kbo)
+    }
+
+    /// То же описание в JSON: `{"weights": {"f": 1}, "variable_weight": 1, "precedence": "f > a"}`.
+    pub fn from_json(input: &str) -> Result<Self, String> {
+        let json: KboJson = serde_json::from_str(input).map_err(|e| e.to_string())?;
+        let precedence = Precedence::parse(&json.precedence).map_err(|e| e.message())?;
+        Ok(Kbo::new(json.weights, json.variable_weight, precedence))
+    }
+
+    fn parse_weights(&mut self, parser: &mut Parser) -> Result<(), ParseError> {
+        loop {
+            let symbol = parser.read_token("символ", |c, _| !(c.is_whitespace() || c == ':' || c == ','))?;
+            let span = parser.previous_span();
+            parser.read_exact_char(':')?;
+            let weight = parse_weight(parser)?;
+            if self.weights.insert(symbol.clone(), weight).is_some() {
+                return Err(ParseError::new(ParseErrorKind::DuplicateWeight, span).symbol(symbol));
+            }
+            match parser.peek() {
+                Ok(',') => { parser.next()?; }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    pub fn admissibility(&self, trs: &ParsedDataTRS) -> Vec<AdmissibilityError> {
+        let mut errors = Vec::new();
+        if self.variable_weight == 0 {
+            errors.push(AdmissibilityError::ZeroVariableWeight);
+        }
+
+        let mut symbols: Vec<(&String, i32)> = trs.functions
+            .iter()
+            .map(|(name, arity)| (name, *arity))
+            .chain(trs.constants.iter().map(|name| (name, 0)))
+            .collect();
+        symbols.sort();
+
+        for (symbol, arity) in &symbols {
+            let weight = match self.weights.get(*symbol) {
+                Some(weight) => *weight,
+                None => {
+                    errors.push(AdmissibilityError::MissingWeight { symbol: symbol.to_string() });
+                    continue;
+                }
+            };
+            if *arity == 0 && weight < self.variable_weight {
+                errors.push(AdmissibilityError::LightConstant { symbol: symbol.to_string(), weight });
+            }
+            if *arity == 1 && weight == 0 {
+                let not_greater_than: Vec<String> = symbols
+                    .iter()
+                    .filter(|(other, _)| other != symbol && !self.precedence.greater(symbol, other))
+                    .map(|(other, _)| other.to_string())
+                    .collect();
+                if !not_greater_than.is_empty() {
+                    errors.push(AdmissibilityError::ZeroWeightUnary { symbol: symbol.to_string(), not_greater_than });
+                }
+            }
+        }
+        errors
+    }
+
+    pub fn check(&self, trs: &ParsedDataTRS) -> Result<KboReport, String> {
+        let rules = trs.rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let left = variable_counts(&rule.left, &trs.variables);
+                let right = variable_counts(&rule.right, &trs.variables);
+                let variable_failures = right
+                    .iter()
+                    .filter(|(variable, count)| left.get(*variable).copied().unwrap_or(0) < **count)
+                    .map(|(variable, count)| VariableCount {
+                        variable: variable.clone(),
+                        left: left.get(variable).copied().unwrap_or(0),
+                        right: *count,
+                    })
+                    .collect();
+                let case = self.greater(&rule.left, &rule.right, &trs.variables)?;
+                Ok(KboVerdict {
+                    rule: i,
+                    oriented: case.is_some(),
+                    left_weight: self.weight(&rule.left, &trs.variables)?,
+                    right_weight: self.weight(&rule.right, &trs.variables)?,
+                    variable_failures,
+                    case,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(KboReport { admissibility: self.admissibility(trs), rules })
+    }
+
+    /// Вес терма; символы без заданного веса считаются нулевыми
+    /// (об этом сообщает проверка допустимости).
+    pub fn weight(&self, term: &Term, variables: &HashSet<String>) -> Result<u64, String> {
+        if term.is_variable(variables) {
+            return Ok(self.variable_weight);
+        }
+        let own = self.weights.get(&term.value).copied().unwrap_or(0);
+        term.childs.iter().try_fold(own, |sum, child| {
+            sum.checked_add(self.weight(child, variables)?)
+                .ok_or_else(|| format!("Переполнение при вычислении веса терма {}", term))
+        })
+    }
+
+    /// Случай, по которому `s >kbo t`, или `None`, если `s` не больше `t`.
+    /// Ошибка — переполнение при вычислении весов.
+    pub fn greater(&self, s: &Term, t: &Term, variables: &HashSet<String>) -> Result<Option<KboCase>, String> {
+        if s.is_variable(variables) {
+            return Ok(None);
+        }
+        let left = variable_counts(s, variables);
+        let right = variable_counts(t, variables);
+        if right.iter().any(|(variable, count)| left.get(variable).copied().unwrap_or(0) < *count) {
+            return Ok(None);
+        }
+
+        let (ws, wt) = (self.weight(s, variables)?, self.weight(t, variables)?);
+        if ws != wt {
+            return Ok((ws > wt).then_some(KboCase::Weight));
+        }
+
+        if t.is_variable(variables) {
+            let mut current = s;
+            while current.childs.len() == 1 && current.value == s.value {
+                current = &current.childs[0];
+            }
+            return Ok((current == t).then(|| KboCase::UnaryTower { symbol: s.value.clone() }));
+        }
+
+        if s.value != t.value {
+            return Ok(self.precedence
+                .greater(&s.value, &t.value)
+                .then(|| KboCase::Precedence { greater: s.value.clone(), smaller: t.value.clone() }));
+        }
+
+        let i = match s.childs.iter().zip(&t.childs).position(|(a, b)| a != b) {
+            Some(i) => i,
+            None => return Ok(None),
+        };
+        Ok(self.greater(&s.childs[i], &t.childs[i], variables)?
+            .map(|_| KboCase::Lexicographic { argument: i }))
+    }
+}
+
+impl KboReport {
+    /// Веса допустимы, и все правила ориентированы.
+    pub fn proved(&self) -> bool {
+        self.admissibility.is_empty() && self.rules.iter().all(|verdict| verdict.oriented)
+    }
+}
+
+impl fmt::Display for AdmissibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AdmissibilityError::MissingWeight { symbol } =>
+                write!(f, "Не задан вес символа {}", symbol),
+            AdmissibilityError::ZeroVariableWeight =>
+                write!(f, "Вес переменных должен быть положительным"),
+            AdmissibilityError::LightConstant { symbol, weight } =>
+                write!(f, "Вес константы {} ({}) меньше веса переменных", symbol, weight),
+            AdmissibilityError::ZeroWeightUnary { symbol, not_greater_than } =>
+                write!(f, "Унарный символ {} нулевого веса должен быть больше в прецедентности символов: {}",
+                       symbol, not_greater_than.join(", ")),
+        }
+    }
+}
+
+fn parse_weight(parser: &mut Parser) -> Result<u64, ParseError> {
+    let number = parser.read_token("число", |c, _| c.is_ascii_digit())?;
+    number
+        .parse::<u64>()
+        .map_err(|_| ParseError::new(ParseErrorKind::NumberTooLarge, parser.previous_span()).found(number))
+}
+
+fn variable_counts(term: &Term, variables: &HashSet<String>) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    let mut stack = vec![term];
+    while let Some(current) = stack.pop() {
+        if current.is_variable(variables) {
+            *counts.entry(current.value.clone()).or_insert(0) += 1;
+        }
+        stack.extend(&current.childs);
+    }
+    counts
+}
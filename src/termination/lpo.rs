@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+use std::fmt;
+use crate::models::ParsedDataTRS;
+use crate::models::data_structures::Term;
+use super::precedence::Precedence;
+
+// Ограничение на число перебираемых линейных прецедентностей (8!)
+const PRECEDENCE_LIMIT: usize = 40320;
+
+/// Какой случай определения LPO применён на шаге доказательства `s >lpo t`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+#[serde(tag = "case", rename_all = "snake_case")]
+pub enum LpoCase {
+    /// `t` — переменная, входящая в `s`.
+    Variable,
+    /// Аргумент `s` с номером `argument` (с нуля) равен `t` или больше `t`.
+    Subterm { argument: usize },
+    /// Корневой символ `s` больше корневого символа `t`, `s` больше всех аргументов `t`.
+    Precedence { greater: String, smaller: String },
+    /// Корневые символы совпадают; аргументы сравниваются лексикографически,
+    /// первое различие — в аргументе `argument`.
+    Lexicographic { argument: usize },
+}
+
+/// Доказательство `left >lpo right`; `premises` — доказательства посылок применённого случая.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+pub struct LpoProof {
+    pub left: String,
+    pub right: String,
+    #[serde(flatten)]
+    pub case: LpoCase,
+    pub premises: Vec<LpoProof>,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct LpoVerdict {
+    pub rule: usize,
+    pub oriented: bool,
+    pub proof: Option<LpoProof>,
+}
+
+/// Лексикографический порядок путей для заданной прецедентности.
+#[derive(Debug, Clone)]
+pub struct Lpo {
+    pub precedence: Precedence,
+}
+
+impl Lpo {
+    pub fn new(precedence: Precedence) -> Self {
+        Lpo { precedence }
+    }
+
+    /// Подбирает линейную прецедентность, при которой все правила ориентированы.
+    /// Достаточно перебирать линейные порядки: расширение прецедентности только расширяет LPO.
+    pub fn search(trs: &ParsedDataTRS) -> Result<Option<Lpo>, String> {
+        let mut symbols: Vec<String> = trs.functions.keys().chain(&trs.constants).cloned().collect();
+        symbols.sort();
+        let count = (1..=symbols.len()).try_fold(1usize, |acc, n| acc.checked_mul(n));
+        if count.is_none_or(|count| count > PRECEDENCE_LIMIT) {
+            return Err(format!("Слишком много символов ({}) для перебора прецедентности", symbols.len()));
+        }
+
+        let mut found = None;
+        permutations(&mut symbols, 0, &mut |order| {
+            let lpo = Lpo::new(Precedence::from_order(order));
+            if lpo.check(trs).iter().all(|verdict| verdict.oriented) {
+                found = Some(lpo);
+                return true;
+            }
+            false
+        });
+        Ok(found)
+    }
+
+    pub fn check(&self, trs: &ParsedDataTRS) -> Vec<LpoVerdict> {
+        trs.rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let proof = self.greater(&rule.left, &rule.right, &trs.variables);
+                LpoVerdict { rule: i, oriented: proof.is_some(), proof }
+            })
+            .collect()
+    }
+
+    /// Доказательство `s >lpo t` или `None`, если `s` не больше `t`.
+    pub fn greater(&self, s: &Term, t: &Term, variables: &HashSet<String>) -> Option<LpoProof> {
+        if s.is_variable(variables) {
+            return None;
+        }
+        let proof = |case, premises| Some(LpoProof { left: s.to_string(), right: t.to_string(), case, premises });
+
+        if t.is_variable(variables) {
+            return match contains(s, t) {
+                true => proof(LpoCase::Variable, Vec::new()),
+                false => None,
+            };
+        }
+
+        if let Some(i) = s.childs.iter().position(|argument| argument == t) {
+            return proof(LpoCase::Subterm { argument: i }, Vec::new());
+        }
+
+        if self.precedence.greater(&s.value, &t.value) {
+            if let Some(premises) = self.greater_than_all(s, &t.childs, variables) {
+                let case = LpoCase::Precedence { greater: s.value.clone(), smaller: t.value.clone() };
+                return proof(case, premises);
+            }
+        }
+
+        if s.value == t.value && s.childs.len() == t.childs.len() {
+            if let Some(i) = s.childs.iter().zip(&t.childs).position(|(a, b)| a != b) {
+                if let Some(first) = self.greater(&s.childs[i], &t.childs[i], variables) {
+                    if let Some(rest) = self.greater_than_all(s, &t.childs, variables) {
+                        let mut premises = vec![first];
+                        premises.extend(rest);
+                        return proof(LpoCase::Lexicographic { argument: i }, premises);
+                    }
+                }
+            }
+        }
+
+        for (i, argument) in s.childs.iter().enumerate() {
+            if let Some(premise) = self.greater(argument, t, variables) {
+                return proof(LpoCase::Subterm { argument: i }, vec![premise]);
+            }
+        }
+        None
+    }
+
+    fn greater_than_all(&self, s: &Term, terms: &[Term], variables: &HashSet<String>) -> Option<Vec<LpoProof>> {
+        terms.iter().map(|t| self.greater(s, t, variables)).collect()
+    }
+}
+
+impl LpoProof {
+    fn write(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        let reason = match &self.case {
+            LpoCase::Variable => format!("{} — переменная левой части", self.right),
+            LpoCase::Subterm { argument } if self.premises.is_empty() =>
+                format!("аргумент {} совпадает с правой частью", argument + 1),
+            LpoCase::Subterm { argument } => format!("аргумент {} больше правой части", argument + 1),
+            LpoCase::Precedence { greater, smaller } =>
+                format!("{} > {} в прецедентности, левая часть больше аргументов правой", greater, smaller),
+            LpoCase::Lexicographic { argument } =>
+                format!("корневые символы совпадают, аргумент {} больше, левая часть больше аргументов правой", argument + 1),
+        };
+        writeln!(f, "{}{} >lpo {}: {}", "  ".repeat(depth), self.left, self.right, reason)?;
+        for premise in &self.premises {
+            premise.write(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for LpoProof {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+fn contains(term: &Term, subterm: &Term) -> bool {
+    term == subterm || term.childs.iter().any(|child| contains(child, subterm))
+}
+
+/// Перебирает перестановки `symbols[k..]`; `visit` возвращает `true`, чтобы остановить перебор.
+fn permutations(symbols: &mut Vec<String>, k: usize, visit: &mut impl FnMut(&[String]) -> bool) -> bool {
+    if k == symbols.len() {
+        return visit(symbols);
+    }
+    for i in k..symbols.len() {
+        symbols.swap(k, i);
+        if permutations(symbols, k + 1, visit) {
+            return true;
+        }
+        symbols.swap(k, i);
+    }
+    false
+}
@@ -0,0 +1,164 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::models::{ParsedDataMatrix, ParsedDataTRS};
+use crate::models::data_structures::{MatrixInterpretFunction, Rule, SquareMatrix, Term, Vector};
+
+/// Значение `[t]` для матричной интерпретации: `constant + Σ coefficients[x] * x`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+pub struct LinearForm {
+    pub constant: Vector,
+    pub coefficients: BTreeMap<String, SquareMatrix>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatrixVerdict {
+    /// Все матрицы и вектор `[l]` не меньше, чем у `[r]`, первая компонента вектора больше.
+    Strict,
+    /// Покомпонентно `[l] >= [r]`, но первые компоненты векторов равны.
+    Weak,
+    NotOriented,
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct MatrixRuleVerdict {
+    pub rule: usize,
+    pub left: LinearForm,
+    pub right: LinearForm,
+    pub verdict: MatrixVerdict,
+}
+
+/// Проверка ориентации правил по матричной интерпретации над натуральными числами
+/// (порядок сравнивает векторы по первой компоненте, остальные — нестрого).
+#[derive(Debug)]
+pub struct MatrixOrder<'a> {
+    interpretations: HashMap<String, &'a MatrixInterpretFunction>,
+    dimension: usize,
+}
+
+impl<'a> MatrixOrder<'a> {
+    pub fn new(interpretation: &'a ParsedDataMatrix) -> Self {
+        MatrixOrder {
+            interpretations: interpretation
+                .iter()
+                .map(|function| (function.name.clone(), function))
+                .collect(),
+            dimension: interpretation.first().map_or(1, |function| function.constant.len()),
+        }
+    }
+
+    pub fn interpret(&self, term: &Term, variables: &HashSet<String>) -> Result<LinearForm, String> {
+        if term.is_variable(variables) {
+            return Ok(LinearForm {
+                constant: vec![0; self.dimension],
+                coefficients: BTreeMap::from([(term.value.clone(), identity(self.dimension))]),
+            });
+        }
+
+        let function = match self.interpretations.get(&term.value) {
+            Some(function) => function,
+            None => return Err(format!("Для символа {} не задана интерпретация", term.value)),
+        };
+        if function.matrices.len() != term.childs.len() {
+            return Err(format!("Не совпадает арность символа {} в TRS и в интерпретации", term.value));
+        }
+
+        let overflow = || format!("Переполнение при вычислении интерпретации символа {}", term.value);
+        let mut result = LinearForm { constant: function.constant.clone(), coefficients: BTreeMap::new() };
+        for (matrix, child) in function.matrices.iter().zip(&term.childs) {
+            let child = self.interpret(child, variables)?;
+            let product = multiply_vector(matrix, &child.constant).ok_or_else(overflow)?;
+            add_vector(&mut result.constant, &product).ok_or_else(overflow)?;
+            for (variable, coefficient) in &child.coefficients {
+                let product = multiply(matrix, coefficient).ok_or_else(overflow)?;
+                match result.coefficients.get_mut(variable) {
+                    Some(sum) => add_matrix(sum, &product).ok_or_else(overflow)?,
+                    None => { result.coefficients.insert(variable.clone(), product); }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn check_rule(&self, index: usize, rule: &Rule, variables: &HashSet<String>) -> Result<MatrixRuleVerdict, String> {
+        let left = self.interpret(&rule.left, variables)?;
+        let right = self.interpret(&rule.right, variables)?;
+
+        let zero = vec![vec![0; self.dimension]; self.dimension];
+        let coefficients_geq = right.coefficients.iter().all(|(variable, coefficient)| {
+            geq_matrix(left.coefficients.get(variable).unwrap_or(&zero), coefficient)
+        });
+        let verdict = if !coefficients_geq || !geq_vector(&left.constant, &right.constant) {
+            MatrixVerdict::NotOriented
+        } else if left.constant[0] > right.constant[0] {
+            MatrixVerdict::Strict
+        } else {
+            MatrixVerdict::Weak
+        };
+        Ok(MatrixRuleVerdict { rule: index, left, right, verdict })
+    }
+
+    pub fn check(&self, trs: &ParsedDataTRS) -> Result<Vec<MatrixRuleVerdict>, String> {
+        trs.rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| self.check_rule(i, rule, &trs.variables))
+            .collect()
+    }
+
+    /// Символы, нарушающие монотонность: левый верхний элемент матрицы аргумента равен 0.
+    pub fn non_monotone(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self.interpretations
+            .values()
+            .filter(|function| function.matrices.iter().any(|matrix| matrix[0][0] == 0))
+            .map(|function| function.name.clone())
+            .collect();
+        symbols.sort();
+        symbols
+    }
+}
+
+fn identity(dimension: usize) -> SquareMatrix {
+    (0..dimension)
+        .map(|i| (0..dimension).map(|j| if i == j { 1 } else { 0 }).collect())
+        .collect()
+}
+
+// Вычисления с проверкой переполнения: `None`, если результат не помещается в i64
+fn dot<'a>(row: &[i64], column: impl Iterator<Item = &'a i64>) -> Option<i64> {
+    row.iter().zip(column).try_fold(0i64, |sum, (x, y)| sum.checked_add(x.checked_mul(*y)?))
+}
+
+fn multiply(a: &SquareMatrix, b: &SquareMatrix) -> Option<SquareMatrix> {
+    a.iter()
+        .map(|row| (0..b.len()).map(|j| dot(row, b.iter().map(|b_row| &b_row[j]))).collect())
+        .collect()
+}
+
+fn multiply_vector(a: &SquareMatrix, v: &[i64]) -> Option<Vector> {
+    a.iter().map(|row| dot(row, v.iter())).collect()
+}
+
+fn add_vector(sum: &mut [i64], v: &[i64]) -> Option<()> {
+    for (a, b) in sum.iter_mut().zip(v) {
+        *a = a.checked_add(*b)?;
+    }
+    Some(())
+}
+
+fn add_matrix(sum: &mut SquareMatrix, m: &SquareMatrix) -> Option<()> {
+    for (row, other) in sum.iter_mut().zip(m) {
+        add_vector(row, other)?;
+    }
+    Some(())
+}
+
+fn geq_vector(a: &[i64], b: &[i64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x >= y)
+}
+
+fn geq_matrix(a: &SquareMatrix, b: &SquareMatrix) -> bool {
+    a.iter().zip(b).all(|(x, y)| geq_vector(x, y))
+}
@@ -0,0 +1,21 @@
+pub mod polynomial;
+pub mod smt;
+pub mod search;
+pub mod precedence;
+pub mod lpo;
+pub mod kbo;
+pub mod dependency_pairs;
+pub mod matrix;
+pub mod arctic;
+pub mod monotonicity;
+
+pub use polynomial::{PolynomialOrder, RuleVerdict, Verdict};
+pub use smt::{Constraint, SmtEncoding, Template};
+pub use search::{find_interpretation, interpretation_to_string, SearchOptions};
+pub use precedence::Precedence;
+pub use lpo::{Lpo, LpoCase, LpoProof, LpoVerdict};
+pub use kbo::{AdmissibilityError, Kbo, KboCase, KboReport, KboVerdict, VariableCount};
+pub use dependency_pairs::{DependencyGraph, DependencyPair};
+pub use matrix::{LinearForm, MatrixOrder, MatrixRuleVerdict, MatrixVerdict};
+pub use arctic::{ArcticOrder, ArcticRuleVerdict, ArcticVerdict};
+pub use monotonicity::{check_interpretation, DEFAULT_MINIMUM};
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use num_traits::{Signed, Zero};
+use crate::models::{Expression, ParseError, ParseErrorKind, ParsedDataInterpret};
+use crate::models::polynomial::integer;
+
+/// Минимальное значение носителя по умолчанию: интерпретации рассматриваются над `{1, 2, ...}`.
+pub const DEFAULT_MINIMUM: i64 = 1;
+
+/// Проверка интерпретации над числами не меньше `minimum`: каждая функция строго монотонна
+/// по каждому аргументу, а значения функций и констант не опускаются ниже `minimum`.
+/// Нарушения возвращаются как нефатальные ошибки с позицией определения символа.
+pub fn check_interpretation(interpretation: &ParsedDataInterpret, minimum: i64) -> Vec<ParseError> {
+    let mut diagnostics = Vec::new();
+    for function in interpretation {
+        let definition = function.definition();
+        for argument in &function.variables {
+            if !is_monotone(&definition, argument, minimum, true) {
+                let e = ParseError::new(ParseErrorKind::NonMonotoneInterpretation, function.span)
+                    .symbol(&function.name)
+                    .found(argument)
+                    .non_fatal();
+                diagnostics.push(e);
+            }
+        }
+
+        let values: HashMap<String, i64> = function.variables.iter().map(|v| (v.clone(), minimum)).collect();
+        let value = match definition.evaluate(&values) {
+            Some(value) if value < integer(minimum) => value,
+            _ => continue,
+        };
+        let kind = match function.variables.is_empty() {
+            true => ParseErrorKind::ConstantBelowMinimum,
+            false => ParseErrorKind::ValueBelowMinimum,
+        };
+        let e = ParseError::new(kind, function.span)
+            .symbol(&function.name)
+            .found(value)
+            .expected(minimum)
+            .non_fatal();
+        diagnostics.push(e);
+    }
+    diagnostics
+}
+
+/// Достаточное условие (строгой) монотонности по `variable`. При `minimum >= 1` моном
+/// `x * y` строго растёт по `x`, при `minimum = 0` — нет, поэтому тогда нужен моном от одной `x`.
+fn is_monotone(expression: &Expression, variable: &str, minimum: i64, strict: bool) -> bool {
+    match expression {
+        Expression::Polynomial(polynomial) => {
+            let mut occurrences = polynomial.monomials.iter().filter(|m| m.variables.contains_key(variable));
+            if occurrences.clone().any(|m| m.coefficient.is_negative()) {
+                return false;
+            }
+            !strict || occurrences.any(|m| !m.coefficient.is_zero() && (minimum >= 1 || m.variables.len() == 1))
+        }
+        Expression::Max { max: parts } | Expression::Min { min: parts } =>
+            parts.iter().all(|part| is_monotone(part, variable, minimum, strict)),
+        Expression::Sum { sum } =>
+            sum.iter().all(|part| is_monotone(part, variable, minimum, false))
+                && (!strict || sum.iter().any(|part| is_monotone(part, variable, minimum, true))),
+        Expression::Product { .. } => !strict && !expression.variables().contains(variable),
+    }
+}
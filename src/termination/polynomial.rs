@@ -0,0 +1,150 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::models::{Coefficient, Expression, ParsedDataInterpret, ParsedDataTRS, Polynomial};
+use crate::models::polynomial::serialize_coefficient;
+use crate::models::data_structures::{ParsedInterpretFunction, Rule, Term};
+
+// Значения переменных, перебираемые при поиске контрпримера
+const COUNTEREXAMPLE_BOUND: i64 = 3;
+const COUNTEREXAMPLE_LIMIT: usize = 4096;
+
+/// Проверка `[l] > [r]` для правил TRS по полиномиальной интерпретации над натуральными числами.
+#[derive(Debug)]
+pub struct PolynomialOrder<'a> {
+    interpretations: HashMap<String, &'a ParsedInterpretFunction>,
+    // Наименьшее значение переменных при поиске контрпримера
+    minimum: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[derive(serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Verdict {
+    /// Все коэффициенты `[l] - [r]` неотрицательны, свободный член положителен
+    /// (для max и min — в каждом случае, где это требуется).
+    Proved,
+    /// Покоэффициентное сравнение не сработало, но контрпример не найден.
+    NotProved,
+    /// Набор значений переменных, при котором `[l] <= [r]`.
+    Counterexample {
+        assignment: BTreeMap<String, i64>,
+        #[serde(serialize_with = "serialize_coefficient")]
+        left: Coefficient,
+        #[serde(serialize_with = "serialize_coefficient")]
+        right: Coefficient,
+    },
+}
+
+#[derive(Debug, Clone)]
+#[derive(serde::Serialize)]
+pub struct RuleVerdict {
+    pub rule: usize,
+    pub left: Expression,
+    pub right: Expression,
+    pub difference: Expression,
+    pub verdict: Verdict,
+}
+
+impl<'a> PolynomialOrder<'a> {
+    pub fn new(interpretation: &'a ParsedDataInterpret) -> Self {
+        PolynomialOrder {
+            interpretations: interpretation
+                .iter()
+                .map(|function| (function.name.clone(), function))
+                .collect(),
+            minimum: 0,
+        }
+    }
+
+    /// Контрпример ищется среди значений переменных не меньше `minimum`
+    /// (носитель `{minimum, minimum + 1, ...}`, как в `check_interpretation`).
+    pub fn with_minimum(mut self, minimum: i64) -> Self {
+        self.minimum = minimum;
+        self
+    }
+
+    /// Полином `[t]`: интерпретации символов композируются снизу вверх по терму.
+    pub fn interpret(&self, term: &Term, variables: &HashSet<String>) -> Result<Polynomial, String> {
+        match self.interpret_expression(term, variables)? {
+            Expression::Polynomial(polynomial) => Ok(polynomial),
+            _ => Err(format!("Интерпретация терма {} содержит max или min и не сводится к полиному", term)),
+        }
+    }
+
+    /// Выражение `[t]`; совпадает с полиномом, если в интерпретациях нет max и min.
+    pub fn interpret_expression(&self, term: &Term, variables: &HashSet<String>) -> Result<Expression, String> {
+        if term.is_variable(variables) {
+            return Ok(Expression::variable(&term.value));
+        }
+
+        let function = match self.interpretations.get(&term.value) {
+            Some(function) => function,
+            None => return Err(format!("Для символа {} не задана интерпретация", term.value)),
+        };
+        if function.variables.len() != term.childs.len() {
+            return Err(format!("Не совпадает арность символа {} в TRS и в интерпретации", term.value));
+        }
+
+        let mut substitution = HashMap::new();
+        for (variable, child) in function.variables.iter().zip(&term.childs) {
+            substitution.insert(variable.clone(), self.interpret_expression(child, variables)?);
+        }
+        function.definition()
+            .compose(&substitution)
+            .map_err(|e| format!("Интерпретация терма {}: {}", term, e))
+    }
+
+    pub fn check_rule(&self, index: usize, rule: &Rule, variables: &HashSet<String>) -> Result<RuleVerdict, String> {
+        let left = self.interpret_expression(&rule.left, variables)?;
+        let right = self.interpret_expression(&rule.right, variables)?;
+        let difference = left.sub(&right);
+
+        let verdict = if difference.is_strictly_positive() {
+            Verdict::Proved
+        } else {
+            find_counterexample(&left, &right, self.minimum).unwrap_or(Verdict::NotProved)
+        };
+
+        Ok(RuleVerdict { rule: index, left, right, difference, verdict })
+    }
+
+    pub fn check(&self, trs: &ParsedDataTRS) -> Result<Vec<RuleVerdict>, String> {
+        trs.rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| self.check_rule(i, rule, &trs.variables))
+            .collect()
+    }
+}
+
+fn find_counterexample(left: &Expression, right: &Expression, minimum: i64) -> Option<Verdict> {
+    let variables: Vec<String> = left.variables().union(&right.variables()).cloned().collect();
+    let mut assignment: HashMap<String, i64> = variables.iter().map(|v| (v.clone(), minimum)).collect();
+
+    for _ in 0..COUNTEREXAMPLE_LIMIT {
+        if let (Some(l), Some(r)) = (left.evaluate(&assignment), right.evaluate(&assignment)) {
+            if l <= r {
+                return Some(Verdict::Counterexample {
+                    assignment: assignment.into_iter().collect(),
+                    left: l,
+                    right: r,
+                });
+            }
+        }
+
+        // Следующий набор значений в порядке счётчика с основанием COUNTEREXAMPLE_BOUND + 1
+        let mut carried = true;
+        for variable in &variables {
+            let value = assignment.get_mut(variable).unwrap();
+            if *value < minimum.saturating_add(COUNTEREXAMPLE_BOUND) {
+                *value += 1;
+                carried = false;
+                break;
+            }
+            *value = minimum;
+        }
+        if carried {
+            break;
+        }
+    }
+    None
+}
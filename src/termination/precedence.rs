@@ -0,0 +1,120 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use crate::models::{ParseError, ParseErrorKind, Parser};
+
+/// Строгий частичный порядок на функциональных символах и константах.
+/// Хранится транзитивное замыкание: `greater[f]` — все символы, меньшие `f`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Precedence {
+    greater: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Precedence {
+    pub fn new() -> Self {
+        Precedence::default()
+    }
+
+    /// Линейный порядок: `order[0] > order[1] > ...`.
+    pub fn from_order(order: &[String]) -> Self {
+        let mut precedence = Precedence::new();
+        for pair in order.windows(2) {
+            precedence.add(&pair[0], &pair[1]);
+        }
+        precedence
+    }
+
+    /// Разбор цепочек вида `f > g > a, h > g`; цепочки разделяются запятыми или переводами строк.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(input);
+        let mut precedence = Precedence::new();
+        loop {
+            parser.skip_whitespace();
+            if parser.peek().is_err() {
+                break;
+            }
+            precedence.parse_line(&mut parser)?;
+        }
+        Ok(precedence)
+    }
+
+    /// Разбор цепочек, разделённых запятыми, до конца строки.
+    pub fn parse_line(&mut self, parser: &mut Parser) -> Result<(), ParseError> {
+        let mut greater = parser.read_token("символ", is_precedence_symbol_char)?;
+        loop {
+            match parser.peek() {
+                Ok('>') => { parser.next()?; }
+                Ok(',') => {
+                    parser.next()?;
+                    parser.skip_whitespace();
+                    greater = parser.read_token("символ", is_precedence_symbol_char)?;
+                    continue;
+                }
+                Ok('\n') | Ok('\r') | Err(_) => return Ok(()),
+                Ok(_) => return Err(parser.unexpected_symbol_error("'>' или ','")),
+            }
+            parser.skip_whitespace();
+            let smaller = parser.read_token("символ", is_precedence_symbol_char)?;
+            if !self.add(&greater, &smaller) {
+                return Err(ParseError::new(ParseErrorKind::PrecedenceCycle, parser.previous_span())
+                    .symbol(format!("{} > {}", greater, smaller)));
+            }
+            greater = smaller;
+        }
+    }
+
+    /// Добавляет `f > g` вместе с транзитивными следствиями;
+    /// `false`, если это нарушает строгость порядка (порядок не изменяется).
+    pub fn add(&mut self, f: &str, g: &str) -> bool {
+        if f == g || self.greater(g, f) {
+            return false;
+        }
+        let mut above: Vec<String> = self.greater
+            .iter()
+            .filter(|(_, smaller)| smaller.contains(f))
+            .map(|(symbol, _)| symbol.clone())
+            .collect();
+        above.push(f.to_string());
+        let mut below: Vec<String> = self.greater.get(g).into_iter().flatten().cloned().collect();
+        below.push(g.to_string());
+
+        for a in &above {
+            self.greater.entry(a.clone()).or_default().extend(below.iter().cloned());
+        }
+        true
+    }
+
+    pub fn greater(&self, f: &str, g: &str) -> bool {
+        self.greater.get(f).is_some_and(|smaller| smaller.contains(g))
+    }
+
+    /// Пары `f > g` без транзитивных следствий, в алфавитном порядке.
+    pub fn pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        for (f, smaller) in &self.greater {
+            for g in smaller {
+                let implied = smaller.iter().any(|h| h != g && self.greater(h, g));
+                if !implied {
+                    pairs.push((f.clone(), g.clone()));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+impl fmt::Display for Precedence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pairs: Vec<String> = self.pairs().iter().map(|(a, b)| format!("{} > {}", a, b)).collect();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+impl serde::Serialize for Precedence {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+fn is_precedence_symbol_char(c: char, _: Option<char>) -> bool {
+    !(c.is_whitespace() || c == ',' || c == '>')
+}
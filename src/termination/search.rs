@@ -0,0 +1,146 @@
+use std::collections::{HashMap, HashSet};
+use num_traits::One;
+use crate::models::{ParsedDataInterpret, ParsedDataTRS, Polynomial};
+use crate::models::polynomial::integer;
+use crate::models::data_structures::ParsedInterpretFunction;
+use super::smt::{Constraint, SmtEncoding, Template};
+
+/// Параметры перебора интерпретаций.
+#[derive(Debug, Clone, Copy)]
+pub struct SearchOptions {
+    /// Коэффициенты перебираются от 0 до `max_coefficient` включительно.
+    pub max_coefficient: i64,
+    pub template: Template,
+    /// Ограничение на число проверенных частичных наборов коэффициентов.
+    pub max_steps: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions { max_coefficient: 3, template: Template::Linear, max_steps: 1_000_000 }
+    }
+}
+
+/// Ищет интерпретацию всех символов TRS, строго ориентирующую все правила.
+///
+/// Коэффициенты шаблонов (см. `SmtEncoding`) перебираются с возвратом: ограничение
+/// проверяется, как только назначены все входящие в него неизвестные.
+/// `Ok(None)` — интерпретации с такими коэффициентами нет, `Err` — исчерпан лимит шагов.
+pub fn find_interpretation(trs: &ParsedDataTRS, options: &SearchOptions) -> Result<Option<ParsedDataInterpret>, String> {
+    let encoding = SmtEncoding::with_template(trs, &Vec::new(), options.template)?;
+
+    // Нулевую константу нельзя записать во входном формате интерпретации
+    let constants: HashSet<String> = encoding.interpretation
+        .iter()
+        .filter(|function| function.variables.is_empty())
+        .flat_map(|function| function.polynomial.variables())
+        .collect();
+
+    let index: HashMap<&String, usize> = encoding.unknowns.iter().enumerate().map(|(i, u)| (u, i)).collect();
+    let mut checks: Vec<Vec<&Constraint>> = vec![Vec::new(); encoding.unknowns.len()];
+    for constraint in &encoding.constraints {
+        match constraint.expression.variables().iter().map(|v| index[v]).max() {
+            Some(last) => checks[last].push(constraint),
+            None if constraint.expression.constant_term() < integer(constraint.bound) => return Ok(None),
+            None => (),
+        }
+    }
+
+    let mut search = Search {
+        unknowns: &encoding.unknowns,
+        constants: &constants,
+        checks: &checks,
+        options,
+        assignment: HashMap::new(),
+        steps: 0,
+    };
+    if !search.assign(0)? {
+        return Ok(None);
+    }
+
+    let values: HashMap<String, Polynomial> = search.assignment
+        .iter()
+        .map(|(unknown, value)| (unknown.clone(), Polynomial::constant(*value)))
+        .collect();
+    encoding.interpretation
+        .iter()
+        .map(|function| {
+            let polynomial = function.polynomial.compose(&values)?;
+            Ok(ParsedInterpretFunction {
+                name: function.name.clone(),
+                variables: function.variables.clone(),
+                expression: polynomial.to_string(),
+                polynomial,
+                piecewise: None,
+                span: function.span,
+            })
+        })
+        .collect::<Result<_, String>>()
+        .map(Some)
+}
+
+/// Запись интерпретации во входном формате (`f(x1, x2) = 2*x1{2} + x2 + 1`).
+pub fn interpretation_to_string(interpretation: &ParsedDataInterpret) -> String {
+    let mut result = String::new();
+    for function in interpretation {
+        let monomials: Vec<String> = function.polynomial.monomials
+            .iter()
+            .rev()
+            .map(|monomial| {
+                let mut text = String::new();
+                if !monomial.coefficient.is_one() || monomial.is_constant() {
+                    text.push_str(&monomial.coefficient.to_string());
+                    if !monomial.is_constant() {
+                        text.push('*');
+                    }
+                }
+                for (variable, degree) in &monomial.variables {
+                    text.push_str(variable);
+                    if *degree != 1 {
+                        text.push_str(&format!("{{{}}}", degree));
+                    }
+                }
+                text
+            })
+            .collect();
+        match function.variables.is_empty() {
+            true => result.push_str(&format!("{} = {}\n", function.name, monomials.join(" + "))),
+            false => result.push_str(&format!("{}({}) = {}\n", function.name, function.variables.join(", "), monomials.join(" + "))),
+        }
+    }
+    result
+}
+
+struct Search<'a> {
+    unknowns: &'a [String],
+    constants: &'a HashSet<String>,
+    checks: &'a [Vec<&'a Constraint>],
+    options: &'a SearchOptions,
+    assignment: HashMap<String, i64>,
+    steps: usize,
+}
+
+impl Search<'_> {
+    fn assign(&mut self, i: usize) -> Result<bool, String> {
+        if i == self.unknowns.len() {
+            return Ok(true);
+        }
+        let unknown = &self.unknowns[i];
+        let min = if self.constants.contains(unknown) { 1 } else { 0 };
+        for value in min..=self.options.max_coefficient {
+            self.steps += 1;
+            if self.steps > self.options.max_steps {
+                return Err(format!("Поиск интерпретации прерван: превышен лимит в {} шагов", self.options.max_steps));
+            }
+            self.assignment.insert(unknown.clone(), value);
+            let consistent = self.checks[i].iter().all(|constraint| {
+                constraint.expression.evaluate_exact(&self.assignment).is_some_and(|v| v >= integer(constraint.bound))
+            });
+            if consistent && self.assign(i + 1)? {
+                return Ok(true);
+            }
+        }
+        self.assignment.remove(unknown);
+        Ok(false)
+    }
+}
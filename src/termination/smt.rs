@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, HashSet};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed};
+use crate::models::{Coefficient, Monomial, ParsedDataInterpret, ParsedDataTRS, Polynomial};
+use crate::models::data_structures::{ParsedInterpretFunction, Span};
+use super::PolynomialOrder;
+
+/// Ограничение `expression >= bound`, где `expression` — полином от неизвестных коэффициентов.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+    pub origin: String,
+    pub expression: Polynomial,
+    pub bound: i64,
+}
+
+/// Вид шаблона для символов без заданной интерпретации.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Template {
+    /// `c_f_0 + c_f_1 * x1 + ... + c_f_n * xn`
+    #[default]
+    Linear,
+    /// Линейный шаблон плюс квадраты аргументов: `... + c_f_(n+i) * xi^2`.
+    Quadratic,
+}
+
+/// Условия завершаемости TRS для интерпретации с неизвестными коэффициентами.
+///
+/// Символы, интерпретация которых не задана, получают линейный шаблон
+/// `c_f_0 + c_f_1 * x1 + ... + c_f_n * xn`. Условия — неотрицательность коэффициентов,
+/// монотонность по каждому аргументу и `[l] - [r] > 0` для каждого правила
+/// (все коэффициенты разности неотрицательны, свободный член положителен).
+#[derive(Debug, Clone)]
+pub struct SmtEncoding {
+    pub unknowns: Vec<String>,
+    pub interpretation: ParsedDataInterpret,
+    pub constraints: Vec<Constraint>,
+}
+
+impl SmtEncoding {
+    pub fn new(trs: &ParsedDataTRS, known: &ParsedDataInterpret) -> Result<Self, String> {
+        SmtEncoding::with_template(trs, known, Template::Linear)
+    }
+
+    pub fn with_template(trs: &ParsedDataTRS, known: &ParsedDataInterpret, template: Template) -> Result<Self, String> {
+        let mut encoding = SmtEncoding { unknowns: Vec::new(), interpretation: Vec::new(), constraints: Vec::new() };
+
+        let mut symbols: Vec<(&String, usize)> = trs.functions
+            .iter()
+            .map(|(name, arity)| (name, *arity as usize))
+            .chain(trs.constants.iter().map(|name| (name, 0)))
+            .collect();
+        symbols.sort();
+
+        for (symbol, arity) in symbols {
+            let function = match known.iter().find(|f| &f.name == symbol) {
+                Some(function) => function.clone(),
+                None => encoding.template(symbol, arity, template, &trs.variables),
+            };
+            encoding.add_symbol_constraints(&function);
+            encoding.interpretation.push(function);
+        }
+
+        let unknowns: HashSet<String> = encoding.unknowns.iter().cloned().collect();
+        let order = PolynomialOrder::new(&encoding.interpretation);
+        let mut constraints = Vec::new();
+        for (i, rule) in trs.rules.iter().enumerate() {
+            let left = order.interpret(&rule.left, &trs.variables)?;
+            let right = order.interpret(&rule.right, &trs.variables)?;
+            let origin = format!("правило {}: {} = {}", i, rule.left, rule.right);
+            for (variables, expression) in split_coefficients(&(&left - &right), &unknowns) {
+                let bound = if variables.is_empty() { 1 } else { 0 };
+                constraints.push(Constraint { origin: origin.clone(), expression, bound });
+            }
+        }
+        encoding.constraints.extend(constraints);
+        Ok(encoding)
+    }
+
+    fn template(&mut self, symbol: &str, arity: usize, template: Template, variables: &HashSet<String>) -> ParsedInterpretFunction {
+        let arguments: Vec<String> = (1..=arity).map(|i| format!("x{}", i)).collect();
+        // Мономы шаблона: 1, x1, ..., xn и для квадратичного шаблона x1^2, ..., xn^2
+        let mut monomials = vec![Polynomial::constant(1)];
+        monomials.extend(arguments.iter().map(|x| Polynomial::variable(x)));
+        if template == Template::Quadratic {
+            monomials.extend(arguments.iter().map(|x| Polynomial::variable(x).pow(2)));
+        }
+
+        let mut polynomial = Polynomial::zero();
+        for (i, monomial) in monomials.iter().enumerate() {
+            let mut name = format!("c_{}_{}", symbol, i);
+            while variables.contains(&name) || self.unknowns.contains(&name) {
+                name.push('_');
+            }
+            polynomial = &polynomial + &(&Polynomial::variable(&name) * monomial);
+            self.unknowns.push(name);
+        }
+        ParsedInterpretFunction {
+            name: symbol.to_string(),
+            variables: arguments,
+            expression: polynomial.to_string(),
+            polynomial,
+            piecewise: None,
+            span: Span::default(),
+        }
+    }
+
+    fn add_symbol_constraints(&mut self, function: &ParsedInterpretFunction) {
+        let unknowns: HashSet<String> = function.polynomial
+            .variables()
+            .into_iter()
+            .filter(|v| !function.variables.contains(v))
+            .collect();
+        let coefficients = split_coefficients(&function.polynomial, &unknowns);
+
+        let origin = format!("неотрицательность коэффициентов {}", function.name);
+        for expression in coefficients.values() {
+            self.push(&origin, expression.clone(), 0);
+        }
+
+        // Достаточное условие монотонности при неотрицательных коэффициентах
+        let origin = format!("монотонность {}", function.name);
+        for argument in &function.variables {
+            let mut sum = Polynomial::zero();
+            for (variables, expression) in &coefficients {
+                if variables.contains_key(argument) {
+                    sum = &sum + expression;
+                }
+            }
+            self.push(&origin, sum, 1);
+        }
+    }
+
+    fn push(&mut self, origin: &str, expression: Polynomial, bound: i64) {
+        self.constraints.push(Constraint { origin: origin.to_string(), expression, bound });
+    }
+
+    /// Скрипт SMT-LIB 2 (логика QF_NIA); выполнимые ограничения без неизвестных опускаются.
+    pub fn to_smt_lib(&self) -> String {
+        let mut script = String::from("; Условия завершаемости TRS для полиномиальной интерпретации\n");
+        script.push_str("(set-logic QF_NIA)\n");
+        for unknown in &self.unknowns {
+            script.push_str(&format!("(declare-fun {} () Int)\n", smt_symbol(unknown)));
+        }
+        for function in &self.interpretation {
+            match function.variables.is_empty() {
+                true => script.push_str(&format!("; {} = {}\n", function.name, function.polynomial)),
+                false => script.push_str(&format!("; {}({}) = {}\n", function.name, function.variables.join(", "), function.polynomial)),
+            }
+        }
+
+        let mut origin = "";
+        for constraint in &self.constraints {
+            let trivial = constraint.expression.variables().is_empty()
+                && constraint.expression.constant_term() >= Coefficient::from_integer(BigInt::from(constraint.bound));
+            if trivial {
+                continue;
+            }
+            if constraint.origin != origin {
+                origin = &constraint.origin;
+                script.push_str(&format!("; {}\n", origin));
+            }
+            // Рациональные коэффициенты: ограничение домножается на общий знаменатель
+            let denominator = constraint.expression.monomials
+                .iter()
+                .fold(BigInt::one(), |acc, monomial| acc.lcm(monomial.coefficient.denom()));
+            let expression = &constraint.expression * &Polynomial::rational(Coefficient::from_integer(denominator.clone()));
+            let bound = denominator * constraint.bound;
+            script.push_str(&format!("(assert (>= {} {}))\n", smt_polynomial(&expression), smt_integer(&bound)));
+        }
+        script.push_str("(check-sat)\n(get-model)\n");
+        script
+    }
+}
+
+/// Группирует мономы по переменным, не входящим в `unknowns`:
+/// результат — коэффициенты (полиномы от неизвестных) при каждом таком мономе.
+/// Свободный член присутствует всегда, даже если он равен нулю.
+fn split_coefficients(polynomial: &Polynomial, unknowns: &HashSet<String>) -> BTreeMap<BTreeMap<String, u32>, Polynomial> {
+    let mut groups: BTreeMap<BTreeMap<String, u32>, Vec<Monomial>> = BTreeMap::new();
+    groups.insert(BTreeMap::new(), Vec::new());
+    for monomial in &polynomial.monomials {
+        let (coefficient, variables): (BTreeMap<String, u32>, BTreeMap<String, u32>) = monomial.variables
+            .iter()
+            .map(|(v, d)| (v.clone(), *d))
+            .partition(|(v, _)| unknowns.contains(v));
+        groups.entry(variables).or_default().push(Monomial::new(monomial.coefficient.clone(), coefficient));
+    }
+    groups
+        .into_iter()
+        .map(|(variables, monomials)| (variables, Polynomial::from_monomials(monomials)))
+        .collect()
+}
+
+fn smt_polynomial(polynomial: &Polynomial) -> String {
+    let terms: Vec<String> = polynomial.monomials.iter().map(smt_monomial).collect();
+    match terms.len() {
+        0 => "0".to_string(),
+        1 => terms[0].clone(),
+        _ => format!("(+ {})", terms.join(" ")),
+    }
+}
+
+fn smt_monomial(monomial: &Monomial) -> String {
+    let mut factors = Vec::new();
+    if !monomial.coefficient.is_one() || monomial.is_constant() {
+        factors.push(smt_integer(&monomial.coefficient.to_integer()));
+    }
+    for (variable, degree) in &monomial.variables {
+        for _ in 0..*degree {
+            factors.push(smt_symbol(variable));
+        }
+    }
+    match factors.len() {
+        1 => factors[0].clone(),
+        _ => format!("(* {})", factors.join(" ")),
+    }
+}
+
+fn smt_integer(value: &BigInt) -> String {
+    if value.is_negative() {
+        format!("(- {})", value.abs())
+    } else {
+        value.to_string()
+    }
+}
+
+/// Простой символ SMT-LIB, либо символ в `|...|`, если имя содержит другие знаки.
+fn smt_symbol(name: &str) -> String {
+    let simple = !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || "~!@$%^&*_-+=<>.?/".contains(c));
+    if simple {
+        name.to_string()
+    } else {
+        format!("|{}|", name)
+    }
+}
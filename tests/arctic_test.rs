@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::{ParseErrorKind, ParsedDataTRS};
+    use tofl_gpt_parser::models::data_structures::Model;
+    use tofl_gpt_parser::parsers::TrsFormat;
+    use tofl_gpt_parser::server::handlers::{parse_arctic_interpretation, parse_trs};
+    use tofl_gpt_parser::termination::{ArcticOrder, ArcticVerdict};
+
+    fn parse(trs: &str) -> (ParsedDataTRS, Model) {
+        parse_trs(trs, TrsFormat::Course).unwrap()
+    }
+
+    #[test]
+    fn test_arctic_parse() {
+        let (_, model) = parse("variables = x, y\nf(x, y) = g(a)");
+        let input = "f(x, y) = max(x + 1, y, max(x, -inf), -inf)\ng(x) = (x + -1) + 2\na = -inf";
+        let interpretation = parse_arctic_interpretation(input, model).unwrap();
+        // x не больше x + 1 — слагаемое поглощается
+        assert_eq!(interpretation[0].expression, "max(x + 1, y)");
+        assert_eq!(interpretation[1].expression, "x + 1");
+        assert!(interpretation[2].polynomial.is_neg_infinity());
+        assert_eq!(interpretation[2].span.end.column, 8);
+    }
+
+    #[test]
+    fn test_arctic_order() {
+        let (trs, model) = parse("variables = x, y\nf(g(x), y) = g(f(x, y))\nf(x, y) = x\ng(x) = h(x)");
+        let input = "f(x, y) = max(x + 1, y)\ng(x) = x + 2\nh(x) = max(x + 2, 0)";
+        let interpretation = parse_arctic_interpretation(input, model).unwrap();
+        let order = ArcticOrder::new(&interpretation);
+        let verdicts = order.check(&trs).unwrap();
+        assert_eq!(verdicts[0].left.to_string(), "max(x + 3, y)");
+        assert_eq!(verdicts[0].right.to_string(), "max(x + 3, y + 2)");
+        assert!(matches!(&verdicts[0].verdict, ArcticVerdict::Counterexample { left: Some(3), right: Some(4), .. }));
+        assert_eq!(verdicts[1].verdict, ArcticVerdict::Strict);
+        assert_eq!(verdicts[2].verdict, ArcticVerdict::Weak);
+        assert_eq!(order.ill_defined(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_arctic_errors() {
+        let (_, model) = parse("variables = x\nf(x) = a");
+        let errors = parse_arctic_interpretation("f(x) = max(x, z)\na = 0", model).unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::UnknownVariable);
+        assert!(errors[0].fatal);
+
+        let (_, model) = parse("variables = x\nf(x) = a");
+        let errors = parse_arctic_interpretation("f(x) = max(x, 1\na = 0", model).unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedSymbol);
+
+        let (trs, model) = parse("variables = x\nf(x) = a");
+        let interpretation = parse_arctic_interpretation("f(x) = x + -1\na = -1", model).unwrap();
+        assert_eq!(ArcticOrder::new(&interpretation).ill_defined(), vec!["a", "f"]);
+        assert_eq!(ArcticOrder::new(&interpretation).check(&trs).unwrap()[0].verdict, ArcticVerdict::Weak);
+    }
+
+    #[test]
+    fn test_arctic_overflow() {
+        let (_, model) = parse("variables = x\nf(x) = a");
+        let errors = parse_arctic_interpretation("f(x) = x + 9223372036854775807 + 1\na = 0", model).unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::NumberTooLarge);
+        assert_eq!((errors[0].span.start.column, errors[0].span.end.column), (7, 34));
+
+        let (trs, model) = parse("variables = x\nf(f(x)) = a");
+        let interpretation = parse_arctic_interpretation("f(x) = x + 9223372036854775807\na = 0", model).unwrap();
+        let error = ArcticOrder::new(&interpretation).check(&trs).unwrap_err();
+        assert_eq!(error, "Переполнение при вычислении интерпретации символа f");
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use tofl_gpt_parser::cli::{self, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+
+    const TRS: &str = "variables = x, y\nf(x, y) = g(x)\ng(x) = x";
+
+    fn run(args: &[&str], stdin: &str) -> (i32, String, String) {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let code = cli::run(&args, &mut Cursor::new(stdin.to_string()), &mut stdout, &mut stderr);
+        (code, String::from_utf8(stdout).unwrap(), String::from_utf8(stderr).unwrap())
+    }
+
+    fn write_trs(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!("tofl_cli_{}_{}.trs", name, std::process::id()));
+        std::fs::write(&path, TRS).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_server_mode() {
+        assert!(cli::is_server_mode(&[]));
+        assert!(cli::is_server_mode(&["serve".to_string()]));
+        assert!(!cli::is_server_mode(&["parse-trs".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_trs_stdin() {
+        let (code, stdout, _) = run(&["parse-trs"], TRS);
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(stdout, "Переменные: x, y\nПравила:\n  f(x, y) = g(x)\n  g(x) = x\n");
+
+        let (code, stdout, _) = run(&["parse-trs", "--json", "-"], TRS);
+        assert_eq!(code, EXIT_OK);
+        assert!(stdout.starts_with("{\"json_TRS\":[{"));
+    }
+
+    #[test]
+    fn test_parse_trs_errors() {
+        let (code, _, stderr) = run(&["parse-trs"], "variables = x\nf(x = x");
+        assert_eq!(code, EXIT_FAILURE);
+        assert_eq!(stderr, "Ошибки в TRS:\n  Ошибка в строке 2, на позиции 4, ожидалось ), считано '='\n");
+
+        let (code, stdout, stderr) = run(&["parse-trs", "--json"], "variables = x\nf(x = x");
+        assert_eq!(code, EXIT_FAILURE);
+        assert!(stderr.is_empty());
+        assert!(stdout.contains("\"kind\":\"unexpected_symbol\""));
+    }
+
+    #[test]
+    fn test_parse_interpretation() {
+        let trs = write_trs("interp");
+        let (code, stdout, _) = run(&["parse-interp", "--trs", &trs], "f(x, y) = x + y + 1\ng(x) = 2*x");
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(stdout, "f(x, y) = (1 + x + y)\ng(x) = (2 * x)\n");
+
+        let (code, stdout, _) = run(&["parse-interp", "--trs", &trs], "f(x, y) = max(x, 1) + y + 1\ng(x) = min(2*x, x + 3)");
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(stdout, "f(x, y) = max((1 + x + y), (2 + y))\ng(x) = min((2 * x), (3 + x))\n");
+
+        let (code, stdout, stderr) = run(&["parse-interp", "--json", "--trs", &trs], "f(x, y) = x + 1\ng(x) = 2*x");
+        assert_eq!(code, EXIT_OK);
+        assert!(stderr.is_empty());
+        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        assert_eq!(json["diagnostics"][0]["kind"], "non_monotone_interpretation");
+        assert_eq!(json["diagnostics"][0]["fatal"], false);
+
+        let (code, _, stderr) = run(&["parse-interp", "--trs", &trs], "f(x, y) = x");
+        assert_eq!(code, EXIT_FAILURE);
+        assert!(stderr.contains("Функция g была объявлена в TRS, но её нет в интерпретации"));
+    }
+
+    #[test]
+    fn test_check() {
+        let trs = write_trs("check");
+        let (code, stdout, _) = run(&["check", "--trs", &trs], "f(x, y) = x + y + 2\ng(x) = x + 1");
+        assert_eq!(code, EXIT_OK);
+        assert!(stdout.ends_with("Завершаемость доказана\n"));
+
+        let (code, stdout, _) = run(&["check", "--json", "--trs", &trs], "f(x, y) = x + y + 1\ng(x) = 2*x");
+        assert_eq!(code, EXIT_FAILURE);
+        assert!(stdout.contains("\"status\":\"counterexample\""));
+    }
+
+    #[test]
+    fn test_warnings() {
+        let input = "variables = x\nf(x) = g(x)\nf(x) = g(x)\ng(x) = x";
+        let (code, stdout, stderr) = run(&["parse-trs"], input);
+        assert_eq!(code, EXIT_OK);
+        assert!(stderr.is_empty());
+        assert!(stdout.ends_with("  f(x) = g(x)\n  g(x) = x\n"));
+
+        let (code, _, stderr) = run(&["parse-trs", "--warnings"], input);
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(stderr, "Предупреждения в TRS:\n  Ошибка в строке 3, на позиции 0. Правило f(x) = g(x) повторяет правило в строке 2\n");
+
+        let (code, stdout, stderr) = run(&["parse-trs", "--warnings", "--json"], input);
+        assert_eq!(code, EXIT_OK);
+        assert!(stderr.is_empty());
+        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        assert_eq!(json["error_trs"][0]["kind"], "duplicate_rule");
+
+        let path = std::env::temp_dir().join(format!("tofl_cli_warnings_{}.trs", std::process::id()));
+        std::fs::write(&path, "variables = x, y\nf(x, y) = f(x, y)\nf(x, y) = g(x)").unwrap();
+        let path = path.to_string_lossy().to_string();
+        let (code, stdout, stderr) = run(&["check", "--warnings", "--trs", &path], "f(x, y) = x + y + 2\ng(x) = x + 1");
+        assert_eq!(code, EXIT_FAILURE);
+        assert!(stderr.starts_with("Предупреждения в TRS:\n  Ошибка в строке 2, на позиции 0. Левая и правая части правила"));
+        assert!(stdout.ends_with("Завершаемость не доказана\n"));
+
+        assert_eq!(run(&["export-tpdb", "--warnings"], input).0, EXIT_USAGE);
+    }
+
+    #[test]
+    fn test_tpdb() {
+        let (code, stdout, _) = run(&["export-tpdb"], TRS);
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(stdout, "(VAR x y)\n(RULES\n  f(x, y) -> g(x)\n  g(x) -> x\n)\n");
+
+        let (code, stdout, _) = run(&["parse-trs", "--tpdb"], &stdout);
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(stdout, "Переменные: x, y\nПравила:\n  f(x, y) = g(x)\n  g(x) = x\n");
+    }
+
+    #[test]
+    fn test_usage_errors() {
+        assert_eq!(run(&["check"], "").0, EXIT_USAGE);
+        assert_eq!(run(&["unknown"], "").0, EXIT_USAGE);
+        assert_eq!(run(&["parse-trs", "a", "b"], "").0, EXIT_USAGE);
+        assert_eq!(run(&["check", "--trs", "-", "-"], "").0, EXIT_USAGE);
+        let (code, _, stderr) = run(&["parse-trs", "/nonexistent/file.trs"], "");
+        assert_eq!(code, EXIT_USAGE);
+        assert!(stderr.starts_with("Не удалось прочитать файл /nonexistent/file.trs"));
+    }
+
+    #[test]
+    fn test_complete() {
+        let trs = "variables = x\nf(f(x)) = g(x)";
+        let (code, stdout, _) = run(&["complete", "--precedence", "f > g"], trs);
+        assert_eq!(code, EXIT_OK);
+        assert_eq!(stdout, "Переменные: x\nПравила:\n  f(f(x)) = g(x)\n  f(g(x)) = g(f(x))\n");
+
+        let (code, _, stderr) = run(&["complete"], "variables = x, y\nm(x, y) = m(y, x)");
+        assert_eq!(code, EXIT_FAILURE);
+        assert_eq!(stderr, "Уравнение m(x, y) = m(y, x) нельзя ориентировать\n");
+
+        let (code, _, _) = run(&["check", "--kbo", "weights.txt", "--trs", "trs.txt"], "");
+        assert_eq!(code, EXIT_USAGE);
+    }
+
+    #[test]
+    fn test_dependency_graph() {
+        let (code, stdout, _) = run(&["dependency-graph"], "variables = x\nf(s(x)) = f(x)");
+        assert_eq!(code, EXIT_OK);
+        assert!(stdout.starts_with("digraph dependency_graph {\n"));
+
+        let (code, stdout, _) = run(&["dependency-graph", "--json"], "variables = x\nf(s(x)) = f(x)");
+        assert_eq!(code, EXIT_OK);
+        assert!(stdout.contains("\"sccs\":[[0]]"));
+    }
+}
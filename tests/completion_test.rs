@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use tofl_gpt_parser::models::ParsedDataTRS;
+    use tofl_gpt_parser::parsers::TrsFormat;
+    use tofl_gpt_parser::rewriting::{check_local_confluence, complete, CompletionOptions, FailureReason, ReductionOrder};
+    use tofl_gpt_parser::server::handlers::parse_trs;
+    use tofl_gpt_parser::termination::{Kbo, Lpo, Precedence};
+
+    const GROUP: &str = "variables = x, y, z\nm(m(x, y), z) = m(x, m(y, z))\nm(e, x) = x\nm(i(x), x) = e";
+
+    fn trs(input: &str) -> ParsedDataTRS {
+        parse_trs(input, TrsFormat::Course).unwrap().0
+    }
+
+    fn rules(trs: &ParsedDataTRS) -> Vec<String> {
+        trs.rules.iter().map(|rule| format!("{} = {}", rule.left, rule.right)).collect()
+    }
+
+    #[test]
+    fn test_completion_group() {
+        let kbo = Kbo::parse("weights = m: 0, i: 0, e: 1\nprecedence = i > m > e").unwrap();
+        let completed = complete(&trs(GROUP), &ReductionOrder::Kbo(kbo), &CompletionOptions::default()).unwrap();
+        assert_eq!(completed.rules.len(), 10);
+        assert!(rules(&completed).contains(&"i(m(x, y)) = m(i(y), i(x))".to_string()));
+        assert_eq!(check_local_confluence(&completed, 10).locally_confluent, Some(true));
+    }
+
+    #[test]
+    fn test_completion_lpo() {
+        let lpo = Lpo::new(Precedence::parse("f > g").unwrap());
+        let completed = complete(&trs("variables = x\nf(f(x)) = g(x)"), &ReductionOrder::Lpo(lpo), &CompletionOptions::default()).unwrap();
+        assert_eq!(rules(&completed), vec!["f(f(x)) = g(x)", "f(g(x)) = g(f(x))"]);
+    }
+
+    #[test]
+    fn test_completion_failures() {
+        let lpo = ReductionOrder::Lpo(Lpo::new(Precedence::new()));
+        let failure = complete(&trs("variables = x, y\nm(x, y) = m(y, x)"), &lpo, &CompletionOptions::default()).unwrap_err();
+        assert_eq!(failure.reason.to_string(), "Уравнение m(x, y) = m(y, x) нельзя ориентировать");
+        assert!(failure.rules.is_empty());
+
+        let options = CompletionOptions { max_steps: 2, ..CompletionOptions::default() };
+        let kbo = Kbo::parse("weights = m: 0, i: 0, e: 1\nprecedence = i > m > e").unwrap();
+        let failure = complete(&trs(GROUP), &ReductionOrder::Kbo(kbo.clone()), &options).unwrap_err();
+        assert!(matches!(failure.reason, FailureReason::StepLimit { steps: 2 }));
+        assert_eq!(failure.rules.len(), 2);
+
+        let options = CompletionOptions { time_limit: Duration::ZERO, ..CompletionOptions::default() };
+        let failure = complete(&trs(GROUP), &ReductionOrder::Kbo(kbo), &options).unwrap_err();
+        assert!(matches!(failure.reason, FailureReason::TimeLimit));
+
+        let kbo = Kbo::parse("weights = m: 0").unwrap();
+        let failure = complete(&trs(GROUP), &ReductionOrder::Kbo(kbo), &CompletionOptions::default()).unwrap_err();
+        let json = serde_json::to_string(&failure).unwrap();
+        assert!(json.starts_with("{\"reason\":\"inadmissible\",\"errors\":[{\"kind\":\"missing_weight\",\"symbol\":\"e\"}"), "{}", json);
+
+        let kbo = Kbo::parse("weights = f: 18446744073709551615, a: 1").unwrap();
+        let failure = complete(&trs("variables = x\nf(a) = a"), &ReductionOrder::Kbo(kbo), &CompletionOptions::default()).unwrap_err();
+        assert_eq!(failure.reason.to_string(), "Переполнение при вычислении веса терма f(a)");
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::data_structures::Term;
+    use tofl_gpt_parser::models::ParsedDataTRS;
+    use tofl_gpt_parser::parsers::TrsFormat;
+    use tofl_gpt_parser::rewriting::{apply, check_local_confluence, critical_pairs, unify, Joinability};
+    use tofl_gpt_parser::server::handlers::parse_trs;
+
+    fn trs(input: &str) -> ParsedDataTRS {
+        parse_trs(input, TrsFormat::Course).unwrap().0
+    }
+
+    fn t(value: &str, childs: Vec<Term>) -> Term {
+        Term::new(value, childs)
+    }
+
+    #[test]
+    fn test_unify() {
+        let variables = ["x".to_string(), "y".to_string()].into();
+        let s = t("f", vec![t("x", vec![]), t("y", vec![])]);
+        let u = t("f", vec![t("g", vec![t("y", vec![])]), t("a", vec![])]);
+        let mgu = unify(&s, &u, &variables).unwrap();
+        assert_eq!(mgu["x"], t("g", vec![t("a", vec![])]));
+        assert_eq!(mgu["y"], t("a", vec![]));
+        assert_eq!(apply(&s, &mgu), apply(&u, &mgu));
+
+        // Проверка вхождения: x = g(x) не имеет решения
+        let s = t("f", vec![t("x", vec![]), t("x", vec![])]);
+        let u = t("f", vec![t("y", vec![]), t("g", vec![t("y", vec![])])]);
+        assert!(unify(&s, &u, &variables).is_none());
+
+        assert!(unify(&t("f", vec![t("x", vec![])]), &t("g", vec![t("x", vec![])]), &variables).is_none());
+    }
+
+    #[test]
+    fn test_critical_pairs() {
+        let pairs = critical_pairs(&trs("variables = x\nf(f(x)) = g(x)"));
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].position, vec![0]);
+        assert_eq!(pairs[0].peak.to_string(), "f(f(f(x')))");
+        assert_eq!(pairs[0].to_string(), "правила 0 и 0, позиция 1: g(f(x')) ← f(f(f(x'))) → f(g(x'))");
+
+        // Имя x' занято, поэтому переменные переименовываются в x'' и x'''
+        let pairs = critical_pairs(&trs("variables = x, x'\nf(f(x, x'), x) = x"));
+        assert_eq!(pairs[0].peak.to_string(), "f(f(f(x'', x'''), x''), f(x'', x'''))");
+
+        let pairs = critical_pairs(&trs("variables = x\nf(f(x)) = g(x)"));
+        let json = serde_json::to_string(&pairs[0]).unwrap();
+        assert_eq!(json, "{\"outer_rule\":0,\"inner_rule\":0,\"position\":[0],\"peak\":\"f(f(f(x')))\",\"left\":\"g(f(x'))\",\"right\":\"f(g(x'))\"}");
+    }
+
+    #[test]
+    fn test_local_confluence() {
+        let report = check_local_confluence(&trs("variables = x\nf(f(x)) = g(x)"), 5);
+        assert_eq!(report.pairs[0].joinability, Joinability::NotJoinable);
+        assert_eq!(report.locally_confluent, Some(false));
+
+        let report = check_local_confluence(&trs("variables = x\nf(g(x)) = x\ng(a) = a\nf(a) = a"), 5);
+        assert_eq!(report.pairs.len(), 1);
+        assert_eq!(report.pairs[0].joinability, Joinability::Joinable { common: t("a", vec![]) });
+        assert_eq!(report.pairs[0].to_string(), "правила 0 и 1, позиция 1: a ← f(g(a)) → f(a): сводится к a");
+        assert_eq!(report.locally_confluent, Some(true));
+
+        // Бесконечное переписывание: сводимость не устанавливается за ограниченное число шагов
+        let report = check_local_confluence(&trs("variables = x\nf(g(x)) = h(x)\ng(x) = g(s(x))"), 3);
+        assert_eq!(report.locally_confluent, None);
+    }
+}
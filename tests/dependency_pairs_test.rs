@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::data_structures::Model;
+    use tofl_gpt_parser::models::ParsedDataTRS;
+    use tofl_gpt_parser::parsers::TrsFormat;
+    use tofl_gpt_parser::server::handlers::{parse_interpretation, parse_trs};
+    use tofl_gpt_parser::termination::{DependencyGraph, PolynomialOrder, Verdict};
+
+    const ACK: &str = "variables = x, y\nack(zero, y) = s(y)\nack(s(x), zero) = ack(x, s(zero))\nack(s(x), s(y)) = ack(x, ack(s(x), y))";
+
+    fn trs(input: &str) -> ParsedDataTRS {
+        parse_trs(input, TrsFormat::Course).unwrap().0
+    }
+
+    #[test]
+    fn test_dependency_pairs() {
+        let graph = DependencyGraph::new(&trs(ACK));
+        assert_eq!(graph.marked["ack"], "ACK");
+        let pairs: Vec<String> = graph.pairs.iter().map(|p| format!("{} -> {}", p.left, p.right)).collect();
+        assert_eq!(pairs, vec![
+            "ACK(s(x), zero) -> ACK(x, s(zero))",
+            "ACK(s(x), s(y)) -> ACK(x, ack(s(x), y))",
+            "ACK(s(x), s(y)) -> ACK(s(x), y)",
+        ]);
+        assert_eq!(graph.pairs[2].position, vec![1]);
+
+        // Из ACK(x, s(zero)) нельзя попасть в ACK(s(x), zero)
+        assert!(!graph.edges.contains(&(0, 0)));
+        assert_eq!(graph.edges.len(), 8);
+        assert_eq!(graph.sccs, vec![vec![0, 1, 2]]);
+
+        // Правая часть — собственный подтерм левой: пары нет
+        let graph = DependencyGraph::new(&trs("variables = x\nf(g(x)) = g(x)\ng(x) = x\nh(x) = f(x)"));
+        assert_eq!(graph.pairs.len(), 1);
+        assert!(graph.sccs.is_empty());
+    }
+
+    #[test]
+    fn test_marked_name_clash() {
+        let graph = DependencyGraph::new(&trs("variables = x\nf(F(x)) = f(x)\nF(x) = x"));
+        assert_eq!(graph.marked["F"], "F'");
+        assert_eq!(graph.marked["f"], "F''");
+    }
+
+    #[test]
+    fn test_scc_polynomial() {
+        let original = trs("variables = x\nf(s(x)) = f(x)");
+        let graph = DependencyGraph::new(&original);
+        let scc = graph.scc_trs(&original, 0);
+        assert_eq!(scc.functions["F"], 1);
+
+        let model = Model { variables: scc.variables.clone(), constants: scc.constants.clone(), functions: scc.functions.clone() };
+        let interpretation = parse_interpretation("F(x) = x\nf(x) = x\ns(x) = x + 1", model).unwrap();
+        let verdicts = PolynomialOrder::new(&interpretation).check(&scc).unwrap();
+        assert_eq!(verdicts[0].verdict, Verdict::Proved);
+    }
+
+    #[test]
+    fn test_export() {
+        let graph = DependencyGraph::new(&trs("variables = x\nf(s(x)) = f(x)"));
+        assert_eq!(graph.to_dot(), "\
+digraph dependency_graph {
+  p0 [label=\"0: F(s(x)) -> F(x)\"];
+  p0 -> p0;
+  subgraph cluster_0 { label=\"SCC 0\"; p0; }
+}
+");
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(json, "{\"marked\":{\"f\":\"F\"},\"pairs\":[{\"rule\":0,\"position\":[],\"left\":\"F(s(x))\",\"right\":\"F(x)\"}],\"edges\":[[0,0]],\"sccs\":[[0]]}");
+    }
+}
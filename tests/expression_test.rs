@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::{Expression, ParseErrorKind, ParsedDataInterpret, ParsedDataTRS, ParseError};
+    use tofl_gpt_parser::parsers::TrsFormat;
+    use tofl_gpt_parser::server::handlers::{parse_interpretation, parse_trs};
+    use tofl_gpt_parser::termination::{PolynomialOrder, Verdict};
+
+    fn parse(trs: &str, interpretation: &str) -> (ParsedDataTRS, Result<ParsedDataInterpret, Vec<ParseError>>) {
+        let (trs, model) = parse_trs(trs, TrsFormat::Course).unwrap();
+        (trs, parse_interpretation(interpretation, model))
+    }
+
+    #[test]
+    fn test_expression_normalization() {
+        let (_, interpret) = parse("variables = x, y\nf(x, y) = g(x)", "f(x,y) = 2*(x+y){2} - x*y + max(x,1)\ng(x) = (x + 1)(x - 1) + 1");
+        let interpret = interpret.unwrap();
+        assert_eq!(interpret[0].expression, "(2 * (x + y)^2 - x * y + max(x, 1))");
+        assert_eq!(interpret[0].definition().to_string(), "max((x + 3 * x * y + 2 * x^2 + 2 * y^2), (1 + 3 * x * y + 2 * x^2 + 2 * y^2))");
+        assert!(interpret[0].polynomial.is_zero());
+        // Без max и min выражение сводится к полиному
+        assert_eq!(interpret[1].piecewise, None);
+        assert_eq!(interpret[1].polynomial.to_string(), "(x^2)");
+
+        // Число внутри монома должно заканчиваться '*', как в `xy4*xy3`
+        let (_, interpret) = parse("variables = x, y\nf(x, y) = g(x)", "f(x, y) = x2\ng(x) = x");
+        let errors = interpret.unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedSymbol);
+        assert_eq!(errors[0].message(), "Ошибка в строке 1, на позиции 12, ожидалось *, считано '\n'");
+    }
+
+    #[test]
+    fn test_expression_size_limits() {
+        let trs = "variables = x, y, z\nf(x, y, z) = g(x)";
+        let (_, interpret) = parse(trs, "f(x, y, z) = (x + y + z){30}\ng(x) = x{100}");
+        assert_eq!(interpret.unwrap()[0].polynomial.monomials.len(), 496);
+
+        let (_, interpret) = parse(trs, "f(x, y, z) = (x + y){100000}\ng(x) = x");
+        let errors = interpret.unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::DegreeTooLarge);
+        assert_eq!(errors[0].message(), "Ошибка в строке 1, на позиции 21. Степень 100000 больше допустимой 100");
+
+        let (_, interpret) = parse(trs, "f(x, y, z) = (x + y + z){50}\ng(x) = (2*x + 1){20}\nf(x, y, z) = (x + y + z){20}(x + y + z){20}");
+        let errors = interpret.unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.kind == ParseErrorKind::ExpressionTooLarge));
+        assert_eq!(errors[0].message(), "Ошибка в строке 1, на позиции 13. После раскрытия скобок выражение содержит больше 1000 мономов");
+
+        // Ограничения действуют и при подстановке интерпретаций вдоль терма
+        let (trs, interpret) = parse("variables = x\nf(f(f(x))) = x", "f(x) = (x + 1){10}");
+        let error = PolynomialOrder::new(&interpret.unwrap()).check(&trs).unwrap_err();
+        assert_eq!(error, "Интерпретация терма f(f(f(x))): Степень 200 больше допустимой 100");
+
+        let (trs, interpret) = parse("variables = x\nf(f(x)) = x", "f(x) = (x + 1){100}");
+        let error = PolynomialOrder::new(&interpret.unwrap()).check(&trs).unwrap_err();
+        assert_eq!(error, "Интерпретация терма f(f(x)): Степень 200 больше допустимой 100");
+
+        let (trs, interpret) = parse("variables = x, y\nf(f(x, y), y) = x", "f(x, y) = max(x, y){5}(x + y)");
+        assert!(PolynomialOrder::new(&interpret.unwrap()).check(&trs).is_ok());
+    }
+
+    #[test]
+    fn test_expression_negative_values() {
+        let (_, interpret) = parse("variables = x, y\nf(x, y) = g(x)", "f(x, y) = x - y + 1\ng(x) = x{2} - 2*x + 1");
+        let errors = interpret.unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ParseErrorKind::NegativeInterpretation);
+        assert_eq!(errors[0].message(), "Ошибка в строке 1, на позиции 10. Интерпретация символа f принимает отрицательное значение -1 при x = 0, y = 2");
+
+        let expression = Expression::min(vec![Expression::variable("x"), Expression::constant(2)]);
+        assert_eq!(expression.sub(&Expression::constant(1)).find_negative_value().map(|(_, v)| v.to_string()), Some("-1".to_string()));
+    }
+
+    #[test]
+    fn test_expression_termination() {
+        let (trs, interpret) = parse("variables = x\nf(g(x)) = g(f(x))\nf(x) = x", "f(x) = max(x, 1) + 1\ng(x) = 2*x");
+        let verdicts = PolynomialOrder::new(&interpret.unwrap()).check(&trs).unwrap();
+        // max(2x, 1) + 1 против max(2x + 2, 4)
+        assert!(matches!(verdicts[0].verdict, Verdict::Counterexample { .. }));
+        assert_eq!(verdicts[1].verdict, Verdict::Proved);
+        assert_eq!(verdicts[1].difference.to_string(), "max((1), (2 - x))");
+    }
+
+    #[test]
+    fn test_rational_coefficients() {
+        let (trs, interpret) = parse("variables = x\nf(x) = g(x)", "f(x) = 3/2*x + 1\ng(x) = 6/4*x + 99999999999999999999/100000000000000000000");
+        let interpret = interpret.unwrap();
+        assert_eq!(interpret[0].expression, "(3/2 * x + 1)");
+        assert_eq!(interpret[0].polynomial.to_string(), "(1 + 3/2 * x)");
+        let verdicts = PolynomialOrder::new(&interpret).check(&trs).unwrap();
+        assert_eq!(verdicts[0].difference.to_string(), "(1/100000000000000000000)");
+        assert_eq!(verdicts[0].verdict, Verdict::Proved);
+
+        let (_, interpret) = parse("variables = x\nf(x) = g(x)", "f(x) = x + 1/0\ng(x) = x");
+        let errors = interpret.unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::InvalidDenominator);
+        assert_eq!(errors[0].message(), "Ошибка в строке 1, на позиции 13. Знаменатель дроби должен быть положительным, считано 0");
+
+        let (_, interpret) = parse("variables = x\nf(x) = g(x)", "f(x) = x\ng(x) = 2/-3 * x");
+        let errors = interpret.unwrap_err();
+        assert_eq!((errors[0].span.start.column, errors[0].span.end.column), (9, 11));
+        assert_eq!(errors[0].found.as_deref(), Some("-3"));
+    }
+}
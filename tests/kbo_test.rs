@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::{ParseErrorKind, ParsedDataTRS};
+    use tofl_gpt_parser::parsers::TrsFormat;
+    use tofl_gpt_parser::server::handlers::parse_trs;
+    use tofl_gpt_parser::termination::{AdmissibilityError, Kbo, KboCase, VariableCount};
+
+    const GROUP: &str = "variables = x, y, z\nm(m(x, y), z) = m(x, m(y, z))\nm(i(x), x) = e\ni(i(x)) = x\ni(m(x, y)) = m(i(y), i(x))";
+    const GROUP_KBO: &str = "weights = m: 0, i: 0, e: 1\nvariable_weight = 1\nprecedence = i > m > e";
+
+    fn trs(input: &str) -> ParsedDataTRS {
+        parse_trs(input, TrsFormat::Course).unwrap().0
+    }
+
+    #[test]
+    fn test_kbo_group() {
+        let kbo = Kbo::parse(GROUP_KBO).unwrap();
+        let report = kbo.check(&trs(GROUP)).unwrap();
+        assert!(report.admissibility.is_empty());
+        assert!(report.proved());
+
+        let cases: Vec<KboCase> = report.rules.iter().map(|v| v.case.clone().unwrap()).collect();
+        assert_eq!(cases, vec![
+            KboCase::Lexicographic { argument: 0 },
+            KboCase::Weight,
+            KboCase::UnaryTower { symbol: "i".to_string() },
+            KboCase::Precedence { greater: "i".to_string(), smaller: "m".to_string() },
+        ]);
+        assert_eq!((report.rules[1].left_weight, report.rules[1].right_weight), (2, 1));
+    }
+
+    #[test]
+    fn test_kbo_failures() {
+        let kbo = Kbo::parse("weights = f: 1, g: 0\nprecedence = f > g").unwrap();
+        let report = kbo.check(&trs("variables = x\nf(x) = g(g(x, x), a)")).unwrap();
+        assert_eq!(report.admissibility, vec![
+            AdmissibilityError::MissingWeight { symbol: "a".to_string() },
+        ]);
+        let verdict = &report.rules[0];
+        assert!(!verdict.oriented);
+        assert_eq!(verdict.variable_failures, vec![VariableCount { variable: "x".to_string(), left: 1, right: 2 }]);
+
+        let kbo = Kbo::parse("weights = f: 0, g: 0, a: 0").unwrap();
+        let report = kbo.check(&trs("variables = x\nf(x) = g(x)\ng(x) = a")).unwrap();
+        assert_eq!(report.admissibility.len(), 3);
+        assert_eq!(report.admissibility[0].to_string(), "Вес константы a (0) меньше веса переменных");
+        assert_eq!(report.admissibility[1].to_string(),
+                   "Унарный символ f нулевого веса должен быть больше в прецедентности символов: a, g");
+    }
+
+    #[test]
+    fn test_kbo_parse_errors() {
+        let e = Kbo::parse("weights = f: 1, f: 2").unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::DuplicateWeight);
+
+        let e = Kbo::parse("weight = f: 1").unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::UnknownSection);
+        assert_eq!(e.message(), "Ошибка в строке 1, на позиции 0. Неизвестный раздел weight, ожидалось weights, variable_weight или precedence");
+
+        let e = Kbo::parse("weights = f: 99999999999999999999").unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::NumberTooLarge);
+    }
+
+    #[test]
+    fn test_kbo_overflow() {
+        let kbo = Kbo::parse("weights = f: 18446744073709551615, a: 1").unwrap();
+        let error = kbo.check(&trs("variables = x\nf(a) = a")).unwrap_err();
+        assert_eq!(error, "Переполнение при вычислении веса терма f(a)");
+    }
+
+    #[test]
+    fn test_kbo_json() {
+        let kbo = Kbo::from_json("{\"weights\": {\"m\": 0, \"i\": 0, \"e\": 1}, \"precedence\": \"i > m > e\"}").unwrap();
+        assert_eq!(kbo.variable_weight, 1);
+        assert!(kbo.check(&trs(GROUP)).unwrap().proved());
+
+        let report = Kbo::parse("weights = f: 2, a: 1").unwrap().check(&trs("variables = x\nf(a) = a")).unwrap();
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(json, "{\"admissibility\":[],\"rules\":[{\"rule\":0,\"oriented\":true,\"left_weight\":3,\"right_weight\":1,\"variable_failures\":[],\"case\":{\"case\":\"weight\"}}]}");
+    }
+}
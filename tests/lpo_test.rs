@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::{ParseErrorKind, ParsedDataTRS};
+    use tofl_gpt_parser::parsers::TrsFormat;
+    use tofl_gpt_parser::server::handlers::parse_trs;
+    use tofl_gpt_parser::termination::{Lpo, LpoCase, Precedence};
+
+    const ACK: &str = "variables = x, y\nack(zero, y) = s(y)\nack(s(x), zero) = ack(x, s(zero))\nack(s(x), s(y)) = ack(x, ack(s(x), y))";
+
+    fn trs(input: &str) -> ParsedDataTRS {
+        parse_trs(input, TrsFormat::Course).unwrap().0
+    }
+
+    #[test]
+    fn test_precedence_parse() {
+        let precedence = Precedence::parse("f > g > a,\nh > g").unwrap();
+        assert!(precedence.greater("f", "a"));
+        assert!(precedence.greater("h", "a"));
+        assert!(!precedence.greater("f", "h"));
+        assert_eq!(precedence.to_string(), "f > g, g > a, h > g");
+
+        let e = Precedence::parse("f > g\ng > a > f").unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::PrecedenceCycle);
+        assert_eq!(e.message(), "Ошибка в строке 2, на позиции 8. Отношение a > f противоречит заданному ранее порядку");
+
+        let e = Precedence::parse("f g").unwrap_err();
+        assert_eq!(e.message(), "Ошибка в строке 1, на позиции 2, ожидалось '>' или ',', считано 'g'");
+    }
+
+    #[test]
+    fn test_lpo_ackermann() {
+        let trs = trs(ACK);
+        let lpo = Lpo::new(Precedence::parse("ack > s").unwrap());
+        let verdicts = lpo.check(&trs);
+        assert!(verdicts.iter().all(|v| v.oriented));
+
+        let proof = verdicts[2].proof.as_ref().unwrap();
+        assert_eq!(proof.case, LpoCase::Lexicographic { argument: 0 });
+        assert_eq!(proof.to_string(), "\
+ack(s(x), s(y)) >lpo ack(x, ack(s(x), y)): корневые символы совпадают, аргумент 1 больше, левая часть больше аргументов правой
+  s(x) >lpo x: x — переменная левой части
+  ack(s(x), s(y)) >lpo x: x — переменная левой части
+  ack(s(x), s(y)) >lpo ack(s(x), y): корневые символы совпадают, аргумент 2 больше, левая часть больше аргументов правой
+    s(y) >lpo y: y — переменная левой части
+    ack(s(x), s(y)) >lpo s(x): аргумент 1 совпадает с правой частью
+    ack(s(x), s(y)) >lpo y: y — переменная левой части
+");
+
+        let lpo = Lpo::new(Precedence::new());
+        let verdicts = lpo.check(&trs);
+        assert!(!verdicts[0].oriented && verdicts[0].proof.is_none());
+    }
+
+    #[test]
+    fn test_lpo_search() {
+        let lpo = Lpo::search(&trs(ACK)).unwrap().unwrap();
+        assert!(lpo.precedence.greater("ack", "s"));
+
+        assert!(Lpo::search(&trs("variables = x\nf(x) = f(f(x))")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_lpo_json() {
+        let lpo = Lpo::new(Precedence::parse("f > g").unwrap());
+        let verdicts = lpo.check(&trs("variables = x\nf(x) = g(x)"));
+        let json = serde_json::to_string(&verdicts[0]).unwrap();
+        assert_eq!(json, "{\"rule\":0,\"oriented\":true,\"proof\":{\"left\":\"f(x)\",\"right\":\"g(x)\",\"case\":\"precedence\",\"greater\":\"f\",\"smaller\":\"g\",\"premises\":[{\"left\":\"f(x)\",\"right\":\"x\",\"case\":\"variable\",\"premises\":[]}]}}");
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::{ParseErrorKind, ParsedDataTRS};
+    use tofl_gpt_parser::models::data_structures::Model;
+    use tofl_gpt_parser::parsers::TrsFormat;
+    use tofl_gpt_parser::server::handlers::{parse_matrix_interpretation, parse_trs};
+    use tofl_gpt_parser::termination::{MatrixOrder, MatrixVerdict};
+
+    const TRS: &str = "variables = x\nf(f(x)) = f(g(f(x)))";
+
+    fn parse(trs: &str) -> (ParsedDataTRS, Model) {
+        parse_trs(trs, TrsFormat::Course).unwrap()
+    }
+
+    #[test]
+    fn test_matrix_parse() {
+        let (_, model) = parse(TRS);
+        let input = "f(x) = [0, 1] + [[1, 1], [0, 0]] * x\ng(y) = [[1, 0], [0, 0]]*y + [[0, 0], [0, 0]]*y";
+        let interpretation = parse_matrix_interpretation(input, model).unwrap();
+        assert_eq!(interpretation[0].constant, vec![0, 1]);
+        assert_eq!(interpretation[0].matrices, vec![vec![vec![1, 1], vec![0, 0]]]);
+        // Вектор не задан — нулевой
+        assert_eq!(interpretation[1].constant, vec![0, 0]);
+        assert_eq!(interpretation[1].span.end.column, 46);
+    }
+
+    #[test]
+    fn test_matrix_order() {
+        let (trs, model) = parse(TRS);
+        let input = "f(x) = [0, 1] + [[1, 1], [0, 0]] * x\ng(x) = [[1, 0], [0, 0]] * x";
+        let interpretation = parse_matrix_interpretation(input, model).unwrap();
+        let order = MatrixOrder::new(&interpretation);
+        let verdicts = order.check(&trs).unwrap();
+        assert_eq!(verdicts[0].verdict, MatrixVerdict::Strict);
+        assert_eq!(verdicts[0].left.constant, vec![1, 1]);
+        assert_eq!(verdicts[0].right.constant, vec![0, 1]);
+        assert_eq!(verdicts[0].right.coefficients["x"], vec![vec![1, 1], vec![0, 0]]);
+        assert_eq!(order.non_monotone(), Vec::<String>::new());
+
+        let (trs, model) = parse("variables = x\nf(x) = g(x)");
+        let interpretation = parse_matrix_interpretation("f(x) = [[1, 0], [0, 1]] * x\ng(x) = [[1, 0], [0, 1]] * x", model).unwrap();
+        assert_eq!(MatrixOrder::new(&interpretation).check(&trs).unwrap()[0].verdict, MatrixVerdict::Weak);
+
+        let (trs, model) = parse("variables = x\nf(x) = g(x)");
+        let interpretation = parse_matrix_interpretation("f(x) = [5, 0] + [[0, 0], [0, 1]] * x\ng(x) = [[0, 0], [1, 1]] * x", model).unwrap();
+        let order = MatrixOrder::new(&interpretation);
+        assert_eq!(order.check(&trs).unwrap()[0].verdict, MatrixVerdict::NotOriented);
+        assert_eq!(order.non_monotone(), vec!["f", "g"]);
+    }
+
+    #[test]
+    fn test_matrix_overflow() {
+        let (trs, model) = parse(TRS);
+        let input = "f(x) = [1] + [[3037000500]] * x\ng(x) = [[1]] * x";
+        let interpretation = parse_matrix_interpretation(input, model).unwrap();
+        let error = MatrixOrder::new(&interpretation).check(&trs).unwrap_err();
+        assert_eq!(error, "Переполнение при вычислении интерпретации символа f");
+
+        let (_, model) = parse("variables = x\nf(x) = x");
+        let errors = parse_matrix_interpretation("f(x) = [9223372036854775807] + [1] + [[1]] * x", model).unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::NumberTooLarge);
+        assert_eq!((errors[0].span.start.column, errors[0].span.end.column), (31, 34));
+        assert_eq!(errors[0].message(), "Ошибка в строке 1, на позиции 31. Число 9223372036854775807 + 1 слишком велико");
+
+        let (_, model) = parse("variables = x\nf(x) = x");
+        let errors = parse_matrix_interpretation("f(x) = [[9223372036854775807]] * x + [[1]] * x", model).unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::NumberTooLarge);
+        assert_eq!((errors[0].span.start.column, errors[0].span.end.column), (37, 46));
+    }
+
+    #[test]
+    fn test_matrix_errors() {
+        let (_, model) = parse(TRS);
+        let errors = parse_matrix_interpretation("f(x) = [0, 1] + [[1, 1, 0], [0, 0, 0]] * x\ng(x) = [1]", model).unwrap_err();
+        let kinds: Vec<ParseErrorKind> = errors.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![ParseErrorKind::MatrixDimensionMismatch, ParseErrorKind::MatrixDimensionMismatch]);
+        assert_eq!(errors[0].message(), "Ошибка в строке 1, на позиции 16. Размерность 2x3 не совпадает с размерностью интерпретации 2x2");
+        assert_eq!(errors[1].message(), "Ошибка в строке 2, на позиции 7. Размерность 1 не совпадает с размерностью интерпретации 2");
+
+        let (_, model) = parse(TRS);
+        let errors = parse_matrix_interpretation("f(x, y) = [[1]] * z", model).unwrap_err();
+        assert_eq!(errors[0].kind, ParseErrorKind::InterpretationArityMismatch);
+        assert_eq!(errors[1].kind, ParseErrorKind::UnknownVariable);
+        assert!(errors[1].fatal);
+
+        let (_, model) = parse(TRS);
+        let errors = parse_matrix_interpretation("f(x) = [[1]] * x", model).unwrap_err();
+        assert_eq!(errors[0].message(), "Функция g была объявлена в TRS, но её нет в интерпретации");
+    }
+}
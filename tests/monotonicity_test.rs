@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::{ParseErrorKind, ParsedDataInterpret};
+    use tofl_gpt_parser::parsers::TrsFormat;
+    use tofl_gpt_parser::server::handlers::{parse_interpretation, parse_trs, process};
+    use tofl_gpt_parser::termination::check_interpretation;
+
+    fn interpretation(trs: &str, interpretation: &str) -> ParsedDataInterpret {
+        let (_, model) = parse_trs(trs, TrsFormat::Course).unwrap();
+        parse_interpretation(interpretation, model).unwrap()
+    }
+
+    #[test]
+    fn test_monotonicity() {
+        let interpret = interpretation("variables = x, y\nf(x, g(y)) = g(x)", "f(x, y) = x\ng(x) = max(x + 1, 2*x)");
+        let diagnostics = check_interpretation(&interpret, 1);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message(), "Ошибка в строке 1, на позиции 0. Не удалось доказать строгую монотонность интерпретации функции f по аргументу y");
+        assert!(!diagnostics[0].fatal);
+
+        // При нуле в носителе x * y не растёт по x, когда y = 0
+        let interpret = interpretation("variables = x, y\nf(x, y) = g(x)", "f(x, y) = x*y + max(x, y)\ng(x) = x");
+        assert_eq!(check_interpretation(&interpret, 1).len(), 0);
+        let kinds: Vec<(ParseErrorKind, Option<String>)> = check_interpretation(&interpret, 0)
+            .into_iter()
+            .map(|e| (e.kind, e.found))
+            .collect();
+        assert_eq!(kinds, vec![
+            (ParseErrorKind::NonMonotoneInterpretation, Some("x".to_string())),
+            (ParseErrorKind::NonMonotoneInterpretation, Some("y".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_values_below_minimum() {
+        let interpret = interpretation("variables = x\nf(x) = c", "f(x) = 1/2*x + 1/2\nc = 1");
+        let diagnostics = check_interpretation(&interpret, 2);
+        assert_eq!(diagnostics[0].kind, ParseErrorKind::ValueBelowMinimum);
+        assert_eq!(diagnostics[0].message(), "Ошибка в строке 1, на позиции 0. Интерпретация функции f при аргументах, равных 2, принимает значение 3/2, меньшее 2");
+        assert_eq!(diagnostics[1].message(), "Ошибка в строке 2, на позиции 0. Значение константы c равно 1, а должно быть не меньше 2");
+        assert!(check_interpretation(&interpret, 1).is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics_in_response() {
+        let res = process("variables = x, y\nf(x, y) = x", TrsFormat::Course, "f(x, y) = x + 1", 1, false).ok().unwrap();
+        let json = serde_json::to_value(&res).unwrap();
+        assert_eq!(json["diagnostics"][0]["kind"], "non_monotone_interpretation");
+        assert_eq!(json["termination"][0]["verdict"]["status"], "proved");
+    }
+}
@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod tests {
     use std::collections::{HashMap, HashSet};
-    use std::fmt::Debug;
-    use tofl_gpt_parser::models::data_structures::Model;
+    use tofl_gpt_parser::models::data_structures::{Model, Position};
+    use std::io::Read;
+    use tofl_gpt_parser::models::{ParseErrorKind, ParsedData, Polynomial};
     use tofl_gpt_parser::parsers;
     use tofl_gpt_parser::parsers::{Parse, ParserInterpret};
     use tofl_gpt_parser::server;
@@ -11,19 +12,16 @@ mod tests {
     fn test_trs0() {
         let input = "variables = x,y\nf(x,h(y))=h(f(x,"; //EOF error
         let mut parser_trs = parsers::ParserTRS::new(input);
-        match parser_trs.parse() {
-            Ok(res) => {panic!("должна быть eof ошибка")}
-            Err(e) => {}
-        }
+        assert!(parser_trs.parse().is_err(), "должна быть eof ошибка");
     }
 
     #[test]
     fn test_trs1() {
-        let input = "variables = x,y\nf(x,h(y))=h(f(x,y))\ng = f(x, y)";
+        let input = "variables = x,y\nf(x,h(y))=h(f(x,y))\ng(x, y) = f(x, y)";
         //let input = "variables = x\ng(x) = f(f)";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => {}
+            Ok(_) => {}
             Err(e) => { panic!("{:?}", e) }
         }
     }
@@ -33,8 +31,8 @@ mod tests {
         let input = "variables = x\ng(x) = f(f)";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => { panic!("должна вернуться ошибка") }
-            Err(e) => { assert_eq!(e[0], "Ошибка в строке 2, на позиции 9, ожидалась константа или переменная, считана функция") }
+            Ok(_) => { panic!("должна вернуться ошибка") }
+            Err(e) => { assert_eq!(e[0].message(), "Ошибка в строке 2, на позиции 9, ожидалась константа или переменная, считана функция") }
         }
     }
 
@@ -43,8 +41,8 @@ mod tests {
         let input = "variables = x\nf(x) = g\nf(x,y) = k(x)";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => { panic!("должна вернуться ошибка") }
-            Err(e) => { assert_eq!(e[0], "Не совпадает арность функции f, ожидаемое количество аргументов: 1 , считано: 2") }
+            Ok(_) => { panic!("должна вернуться ошибка") }
+            Err(e) => { assert_eq!(e[0].message(), "Не совпадает арность функции f, ожидаемое количество аргументов: 1 , считано: 2") }
         }
     }
 
@@ -53,8 +51,8 @@ mod tests {
         let input = "variables = x,y,x\nf(x) = g\nf(x,y) = k(x)";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => { panic!("должна вернуться ошибка") }
-            Err(e) => { assert_eq!(e[0], "Переменная x объявлена несколько раз") }
+            Ok(_) => { panic!("должна вернуться ошибка") }
+            Err(e) => { assert_eq!(e[0].message(), "Переменная x объявлена несколько раз") }
         }
     }
 
@@ -63,19 +61,217 @@ mod tests {
         let input = "variables  x,y\nf(x) = g\nf(x,y) = k(x)";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => { panic!("должна вернуться ошибка") }
+            Ok(_) => { panic!("должна вернуться ошибка") }
             Err(e) => {
                 println!("{:?}", e)
             }
         }
     }
 
+    #[test]
+    fn test_trs_identifiers() {
+        let input = "variables = x, ys'\nadd(x, succ(ys')) = succ(add(x, ys'))\nadd(x, zero_1) = x";
+        let mut parser_trs = parsers::ParserTRS::new(input);
+        let trs = match parser_trs.parse() {
+            Ok(ParsedData::TRS(trs)) => trs,
+            other => panic!("{:?}", other),
+        };
+        assert!(trs.variables.contains("ys'"));
+        assert!(trs.constants.contains("zero_1"));
+        assert_eq!(trs.functions.get("add"), Some(&2));
+        assert_eq!(trs.functions.get("succ"), Some(&1));
+        assert_eq!(trs.rules[0].left.value, "add");
+        assert_eq!(trs.rules[0].left.childs[1].childs[0].value, "ys'");
+        assert_eq!(trs.rules[0].left.span.end, Position { line: 2, column: 17, offset: 36 });
+    }
+
+    #[test]
+    fn test_trs_identifier_arity() {
+        let input = "variables = x\nplus(x) = x\nplus(x, x) = x";
+        let mut parser_trs = parsers::ParserTRS::new(input);
+        match parser_trs.parse() {
+            Ok(_) => { panic!("должна вернуться ошибка") }
+            Err(e) => { assert_eq!(e[0].message(), "Не совпадает арность функции plus, ожидаемое количество аргументов: 1 , считано: 2") }
+        }
+    }
+
+    #[test]
+    fn test_trs_tpdb() {
+        let input = "(VAR x y)\n(COMMENT сложение (Пеано))\n(RULES\n  plus(x, 0) -> x\n  plus(x,\n       s(y)) -> s(plus(x, y))\n  a() -> 0\n)\n";
+        let mut parser_trs = parsers::ParserTRS::with_format(input, parsers::TrsFormat::Tpdb);
+        let trs = match parser_trs.parse() {
+            Ok(ParsedData::TRS(trs)) => trs,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(trs.rules.len(), 3);
+        assert_eq!(trs.functions.get("plus"), Some(&2));
+        assert!(trs.constants.contains("0") && trs.constants.contains("a"));
+        assert_eq!(trs.rules[1].right.to_string(), "s(plus(x, y))");
+        assert_eq!(trs.rules[1].span.start, Position { line: 5, column: 2, offset: 77 });
+
+        let printed = trs.to_tpdb();
+        assert_eq!(printed, "(VAR x y)\n(RULES\n  plus(x, 0) -> x\n  plus(x, s(y)) -> s(plus(x, y))\n  a -> 0\n)\n");
+        match parsers::ParserTRS::with_format(&printed, parsers::TrsFormat::Tpdb).parse() {
+            Ok(ParsedData::TRS(reparsed)) => assert_eq!(reparsed.rules.len(), 3),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_trs_tpdb_comment_section() {
+        let input = "(VAR x)\n(COMMENT see #1 for details)\n(COMMENT from paper -- section 2 /* draft)\n(RULES f(x) -> x)";
+        match parsers::ParserTRS::with_format(input, parsers::TrsFormat::Tpdb).parse() {
+            Ok(ParsedData::TRS(trs)) => assert_eq!(trs.rules.len(), 1),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_trs_tpdb_errors() {
+        let mut parser_trs = parsers::ParserTRS::with_format("(VAR x)\n(THEORY (AC plus))", parsers::TrsFormat::Tpdb);
+        let e = parser_trs.parse().unwrap_err();
+        assert_eq!(e[0].kind, ParseErrorKind::UnknownSection);
+        assert_eq!(e[0].message(), "Ошибка в строке 2, на позиции 1. Неизвестный раздел THEORY, ожидалось VAR, RULES или COMMENT");
+
+        let mut parser_trs = parsers::ParserTRS::with_format("(VAR x)\n(RULES f(x) ->= x)", parsers::TrsFormat::Tpdb);
+        let e = parser_trs.parse().unwrap_err();
+        assert_eq!(e[0].message(), "Ошибка в строке 2, на позиции 14, ожидалось терм, считано '='");
+
+        let mut parser_trs = parsers::ParserTRS::with_format("(VAR x)\n(RULES f(x) -> y)", parsers::TrsFormat::Tpdb);
+        assert!(parser_trs.parse().is_ok(), "y без объявления в VAR — константа");
+
+        let mut parser_trs = parsers::ParserTRS::with_format("(VAR x y)\n(RULES f(x) -> y)", parsers::TrsFormat::Tpdb);
+        let e = parser_trs.parse().unwrap_err();
+        assert_eq!(e[0].kind, ParseErrorKind::UnboundVariables);
+    }
+
+    #[test]
+    fn test_trs_course_to_tpdb() {
+        let input = "variables = x\nf(g(x), a) = x";
+        let trs = match parsers::ParserTRS::new(input).parse() {
+            Ok(ParsedData::TRS(trs)) => trs,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(trs.to_tpdb(), "(VAR x)\n(RULES\n  f(g(x), a) -> x\n)\n");
+    }
+
+    #[test]
+    fn test_trs_structured_errors() {
+        let input = "variables x, x, y\nf(x = f(x)";
+        let mut parser_trs = parsers::ParserTRS::new(input);
+        let e = parser_trs.parse().unwrap_err();
+        assert_eq!(e.len(), 3);
+        assert_eq!(e[0].kind, ParseErrorKind::MissingVariablesEquals);
+        assert!(!e[0].fatal);
+        assert_eq!(e[1].kind, ParseErrorKind::DuplicateVariable);
+        assert_eq!(e[1].symbol.as_deref(), Some("x"));
+        assert_eq!(e[2].kind, ParseErrorKind::UnexpectedSymbol);
+        assert_eq!(e[2].expected.as_deref(), Some(")"));
+        assert_eq!(e[2].found.as_deref(), Some("="));
+        assert_eq!(e[2].span.start, Position { line: 2, column: 4, offset: 22 });
+        assert!(e[2].fatal);
+    }
+
+    #[test]
+    fn test_trs_error_recovery() {
+        let input = "variables = x, y\nf(x = x\ng(x) = g(g(x))\nh(x) = x)\nf(a) = a\nf(x) = y";
+        let mut parser_trs = parsers::ParserTRS::new(input);
+        let e = parser_trs.parse().unwrap_err();
+        assert_eq!(e.len(), 3);
+        assert!(e.iter().all(|e| e.fatal));
+        assert_eq!(e[0].span.start.line, 2);
+        assert_eq!(e[0].kind, ParseErrorKind::UnexpectedSymbol);
+        assert_eq!(e[1].span.start.line, 4);
+        assert_eq!(e[1].found.as_deref(), Some(")"));
+        assert_eq!(e[2].kind, ParseErrorKind::UnboundVariables);
+        assert_eq!(e[2].span.start.line, 6);
+    }
+
+    #[test]
+    fn test_trs_variable_condition_per_rule() {
+        let input = "variables = x, y\nf(x) = a\ng(a, y) = h(x, y)";
+        let e = parsers::ParserTRS::new(input).parse().unwrap_err();
+        assert_eq!(e.len(), 1);
+        assert_eq!(e[0].kind, ParseErrorKind::UnboundVariables);
+        assert_eq!(e[0].symbol.as_deref(), Some("x"));
+        assert_eq!(e[0].span.start, Position { line: 3, column: 0, offset: 26 });
+        assert_eq!(e[0].span.end, Position { line: 3, column: 17, offset: 43 });
+
+        let e = parsers::ParserTRS::new("variables = x\nx = f(x)").parse().unwrap_err();
+        assert_eq!(e[0].message(), "Ошибка в строке 2, на позиции 0. Левая часть правила не может быть переменной x");
+
+        let trs = match parsers::ParserTRS::new("variables = x, y\nf(x, g(y)) = x").parse() {
+            Ok(ParsedData::TRS(trs)) => trs,
+            other => panic!("{:?}", other),
+        };
+        let variables: Vec<String> = trs.rules[0].variables(&trs.variables).into_iter().collect();
+        assert_eq!(variables, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn test_trs_well_formed() {
+        let e = parsers::ParserTRS::with_format("(VAR x)\n(RULES\n  f(x) -> x\n  x -> f(x)\n)", parsers::TrsFormat::Tpdb)
+            .parse()
+            .unwrap_err();
+        assert_eq!(e.len(), 1);
+        assert_eq!(e[0].kind, ParseErrorKind::VariableLeftSide);
+        assert_eq!(e[0].span.start, Position { line: 4, column: 2, offset: 29 });
+        assert!(e[0].fatal);
+
+        // Переменная в левой части сообщается и при синтаксической ошибке в другом правиле
+        let e = parsers::ParserTRS::new("variables = x\nx = f(x)\nf(g(x) = a").parse().unwrap_err();
+        let kinds: Vec<(ParseErrorKind, u32, bool)> = e.iter().map(|e| (e.kind, e.span.start.line, e.fatal)).collect();
+        assert_eq!(kinds, vec![
+            (ParseErrorKind::UnexpectedSymbol, 3, true),
+            (ParseErrorKind::VariableLeftSide, 2, true),
+        ]);
+
+        let (trs, _) = server::handlers::parse_trs("variables = x\nf(x) = g(x)\ng(x) = g(x)\nf(x) = g(x)", parsers::TrsFormat::Course).unwrap();
+        assert!(trs.check_well_formed(false).is_empty());
+        let warnings = trs.check_well_formed(true);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|e| !e.fatal));
+        assert_eq!(warnings[0].message(), "Ошибка в строке 3, на позиции 0. Левая и правая части правила g(x) = g(x) совпадают");
+        assert_eq!(warnings[1].message(), "Ошибка в строке 4, на позиции 0. Правило f(x) = g(x) повторяет правило в строке 2");
+        assert_eq!(warnings[1].expected, None);
+        assert_eq!(warnings[1].related_line, Some(2));
+        assert_eq!(serde_json::to_value(&warnings[1]).unwrap()["related_line"], 2);
+        assert!(serde_json::to_value(&warnings[0]).unwrap().get("related_line").is_none());
+
+        let res = server::handlers::process("variables = x\nf(x) = x\nf(x) = x", parsers::TrsFormat::Course, "f(x) = x + 1", 1, true).ok().unwrap();
+        assert_eq!(res.error_trs.len(), 1);
+        assert_eq!(res.error_trs[0].kind, ParseErrorKind::DuplicateRule);
+    }
+
+    #[test]
+    fn test_comments() {
+        let input = "# сложение\nvariables = x, y -- переменные\n\nadd(x, s(y)) = s(add(x, y)) /* шаг */\n/* база\n   рекурсии */ add(x, z) = x\n";
+        let (trs, model) = server::handlers::parse_trs(input, parsers::TrsFormat::Course).unwrap();
+        assert_eq!(trs.rules.len(), 2);
+        assert_eq!(trs.rules[1].span.start, Position { line: 6, column: 15, offset: 137 });
+
+        let interpretation = "add(x, y) = x + 2*y # линейная\n-- константы\nz = 1 /* минимум */\ns(x) = x + 1 --шаг\n";
+        let interpret = server::handlers::parse_interpretation(interpretation, model).unwrap();
+        assert_eq!(interpret.len(), 3);
+        assert_eq!(interpret[2].expression, "(x + 1)");
+
+        // `--` сразу после операнда — не комментарий
+        let (_, model) = server::handlers::parse_trs(input, parsers::TrsFormat::Course).unwrap();
+        let e = server::handlers::parse_interpretation("add(x, y) = x--1\nz = 1\ns(x) = x + 1", model).unwrap_err();
+        assert_eq!(e.len(), 1);
+        assert_eq!(e[0].span.start, Position { line: 1, column: 14, offset: 14 });
+
+        let e = parsers::ParserTRS::new("variables = x\nf(x) = x /* x\n").parse().unwrap_err();
+        assert_eq!(e[0].kind, ParseErrorKind::UnterminatedComment);
+        assert_eq!(e[0].span.start, Position { line: 2, column: 9, offset: 23 });
+    }
+
     #[test]
     fn test_trs_deep_nesting_correct_1() {
         let input = "variables = x\na(b(c(d(e(f(x)))))) = g(h(i(j(k(l(x))))))\n";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => {
+            Ok(_) => {
             }
             Err(e) => {
                 panic!("{:?}", e)
@@ -88,7 +284,7 @@ mod tests {
         let input = "variables = x\na(b(a(b(a(b(a(b(x)))))))) = a(x)\n";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => {
+            Ok(_) => {
             }
             Err(e) => {
                 panic!("{:?}", e)
@@ -101,7 +297,7 @@ mod tests {
         let input = "variables = x,y,z\nf(g(h(i(j(x)))),k(l(m(n(y)))),o(p(q(r(s(z)))))) = t(u(v(w(x))))\n";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => { }
+            Ok(_) => { }
             Err(e) => {
                 panic!("{:?}", e)
             }
@@ -114,7 +310,7 @@ mod tests {
         let input = "variables = x\nf(g(h(i(j(k(l(m(n(o(p(x))))))))))) = q(x)\nf(a,b) = c\n";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -125,22 +321,39 @@ mod tests {
         // Здесь отсутствует закрывающая скобка для функции 'a'
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
 
+    #[test]
+    fn test_trs_spans() {
+        let input = "variables = ж\nf(ж) = g(ж)\nh(c) = c";
+        let mut parser_trs = parsers::ParserTRS::new(input);
+        let trs = match parser_trs.parse() {
+            Ok(ParsedData::TRS(trs)) => trs,
+            other => panic!("{:?}", other),
+        };
+        let rule = &trs.rules[0];
+        assert_eq!(rule.left.span.start, Position { line: 2, column: 0, offset: 15 });
+        assert_eq!(rule.left.span.end, Position { line: 2, column: 4, offset: 20 });
+        assert_eq!(rule.left.childs[0].span.start, Position { line: 2, column: 2, offset: 17 });
+        assert_eq!(rule.right.span.start, Position { line: 2, column: 7, offset: 23 });
+        assert_eq!(rule.span.end, rule.right.span.end);
+        assert_eq!(trs.rules[1].right.span.start, Position { line: 3, column: 7, offset: 36 });
+    }
+
     #[test]
     fn test_interpret_function_constant_not_declared() { //Функция была объявлена в TRS, но её нет в интерпретации
         let input1 = "F(m,n) = m+n\n";
         let mut functions = HashMap::new();
-        functions.insert('F', 2);
-        functions.insert('A', 1);
+        functions.insert("F".to_string(), 2);
+        functions.insert("A".to_string(), 1);
         let mut variables = HashSet::new();
-        variables.insert('m');
-        variables.insert('n');
+        variables.insert("m".to_string());
+        variables.insert("n".to_string());
         let mut constants = HashSet::new();
-        constants.insert('p');
+        constants.insert("p".to_string());
         let mut parser_interpret = ParserInterpret::new(input1, Model{
             variables,
             constants,
@@ -149,20 +362,46 @@ mod tests {
 
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
 
+    #[test]
+    fn test_interpret_error_recovery() { //Ошибка в первой строке не скрывает ошибки в остальных
+        let input = "F(m,n) = m+*n\nG(x, y) = x\nc = 1)\n";
+        let mut functions = HashMap::new();
+        functions.insert("F".to_string(), 2);
+        functions.insert("G".to_string(), 1);
+        functions.insert("H".to_string(), 1);
+        let mut constants = HashSet::new();
+        constants.insert("c".to_string());
+        let mut parser_interpret = ParserInterpret::new(input, Model{
+            variables: HashSet::new(),
+            constants,
+            functions,
+        });
+
+        let e = parser_interpret.parse().unwrap_err();
+        let kinds: Vec<(ParseErrorKind, u32, bool)> = e.iter().map(|e| (e.kind, e.span.start.line, e.fatal)).collect();
+        assert_eq!(kinds, vec![
+            (ParseErrorKind::UnexpectedSymbol, 1, true),
+            (ParseErrorKind::InterpretationArityMismatch, 2, false),
+            (ParseErrorKind::UnexpectedSymbol, 3, true),
+            (ParseErrorKind::MissingFunctionInterpretation, 4, false),
+        ]);
+        assert_eq!(e[3].symbol.as_deref(), Some("H"));
+    }
+
     #[test]
     fn test_interpret_function_not_declared() { //Функция была объявлена в TRS, но её нет в интерпретации
         let input1 = "F(m,n) = m+n\n";
         let mut functions = HashMap::new();
-        functions.insert('F', 2);
-        functions.insert('A', 1);
+        functions.insert("F".to_string(), 2);
+        functions.insert("A".to_string(), 1);
         let mut variables = HashSet::new();
-        variables.insert('m');
-        variables.insert('n');
+        variables.insert("m".to_string());
+        variables.insert("n".to_string());
         let mut parser_interpret = ParserInterpret::new(input1, Model{
             variables,
             constants: HashSet::new(),
@@ -170,27 +409,24 @@ mod tests {
         });
 
         let res = parser_interpret.parse();
-        match res {
-            Ok(res) => {panic!("должна быть ошибка")}
-            Err(e) => {  }
-        }
+        assert!(res.is_err(), "должна быть ошибка");
     }
 
     #[test]
     fn test_interpret_constant_not_declared() { //Константа была объявлена в TRS, но её нет в интерпретации
         let input1 = "F(m,n) = m+n\n";
         let mut functions = HashMap::new();
-        functions.insert('F', 2);
+        functions.insert("F".to_string(), 2);
         let mut variables = HashSet::new();
-        variables.insert('m');
-        variables.insert('n');
+        variables.insert("m".to_string());
+        variables.insert("n".to_string());
         let mut constants = HashSet::new();
-        constants.insert('p');
+        constants.insert("p".to_string());
         let mut parser_interpret = ParserInterpret::new(input1, Model{ variables, constants, functions, });
 
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -203,7 +439,7 @@ mod tests {
         });
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -216,7 +452,7 @@ mod tests {
         });
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -224,14 +460,14 @@ mod tests {
     #[test]
     fn test_interpret_f_not_declared_in_trs() { //Константа была объявлена в TRS, но её нет в интерпретации
         let input1 = "F(m,n) = 2m+n\n";
-        let mut functions = HashMap::new();
-        let mut variables = HashSet::new();
-        let mut constants = HashSet::new();
+        let functions = HashMap::new();
+        let variables = HashSet::new();
+        let constants = HashSet::new();
         let mut parser_interpret = ParserInterpret::new(input1, Model{ variables, constants, functions, });
 
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -240,14 +476,14 @@ mod tests {
     fn test_interpret_0_coef() { //Коэффициент не может быть равен 0
         let input1 = "F(m) = 0*m\n";
         let mut functions = HashMap::new();
-        functions.insert('F', 1);
+        functions.insert("F".to_string(), 1);
         let mut variables = HashSet::new();
-        variables.insert('m');
-        let mut constants = HashSet::new();
+        variables.insert("m".to_string());
+        let constants = HashSet::new();
         let mut parser_interpret = ParserInterpret::new(input1, Model{ variables, constants, functions, });
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -256,15 +492,15 @@ mod tests {
     fn test_interpret_expected_alphabetic_var() { //Ожидалась буква (в названии переменной)
         let input1 = "F(m,n) = m+🔥";
         let mut functions = HashMap::new();
-        functions.insert('F', 2);
+        functions.insert("F".to_string(), 2);
         let mut variables = HashSet::new();
-        variables.insert('m');
-        variables.insert('n');
-        let mut constants = HashSet::new();
+        variables.insert("m".to_string());
+        variables.insert("n".to_string());
+        let constants = HashSet::new();
         let mut parser_interpret = ParserInterpret::new(input1, Model{ variables, constants, functions, });
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -273,15 +509,15 @@ mod tests {
     fn test_interpret_expected_bracket() { // Ожидалось ',' или ')', считано что-то
         let input1 = "F(m,n| = m+n";
         let mut functions = HashMap::new();
-        functions.insert('F', 2);
+        functions.insert("F".to_string(), 2);
         let mut variables = HashSet::new();
-        variables.insert('m');
-        variables.insert('n');
-        let mut constants = HashSet::new();
+        variables.insert("m".to_string());
+        variables.insert("n".to_string());
+        let constants = HashSet::new();
         let mut parser_interpret = ParserInterpret::new(input1, Model{ variables, constants, functions, });
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -290,15 +526,15 @@ mod tests {
     fn test_interpret_expected_bracket_eof() { //ожидалось: ')' или ',', считано EOF
         let input1 = "F(m,n";
         let mut functions = HashMap::new();
-        functions.insert('F', 2);
+        functions.insert("F".to_string(), 2);
         let mut variables = HashSet::new();
-        variables.insert('m');
-        variables.insert('n');
-        let mut constants = HashSet::new();
+        variables.insert("m".to_string());
+        variables.insert("n".to_string());
+        let constants = HashSet::new();
         let mut parser_interpret = ParserInterpret::new(input1, Model{ variables, constants, functions, });
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -307,14 +543,14 @@ mod tests {
     fn test_interpret_expected_plus() { //ожидалось: ')' или ',', считано EOF
         let input1 = "F(m) = m,";
         let mut functions = HashMap::new();
-        functions.insert('F', 1);
+        functions.insert("F".to_string(), 1);
         let mut variables = HashSet::new();
-        variables.insert('m');
-        let mut constants = HashSet::new();
+        variables.insert("m".to_string());
+        let constants = HashSet::new();
         let mut parser_interpret = ParserInterpret::new(input1, Model{ variables, constants, functions, });
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => { println!("{:?}", e) }
         }
     }
@@ -333,7 +569,7 @@ mod tests {
         let mut parser_interpret = ParserInterpret::new(input1, model);
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {}
+            Ok(_) => {}
             Err(e) => { panic!("{:?}", e) }
         }
     }
@@ -342,9 +578,9 @@ mod tests {
     fn test_interpret2() {
         let input = "f(x)=x+1";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 1);
+        functions.insert("f".to_string(), 1);
 
         let mut parser = parsers::ParserInterpret::new(input, Model{
             variables,
@@ -353,7 +589,7 @@ mod tests {
         });
 
         match parser.parse() {
-            Ok(res) => {}
+            Ok(_) => {}
             Err(e) => { panic!("{:?}", e) }
         }
     }
@@ -362,9 +598,9 @@ mod tests {
     fn test_interpret3() {
         let input = "f(x)=x+1\n";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 1);
+        functions.insert("f".to_string(), 1);
 
         let mut parser = parsers::ParserInterpret::new(input, Model{
             variables,
@@ -373,7 +609,7 @@ mod tests {
         });
 
         match parser.parse() {
-            Ok(res) => {}
+            Ok(_) => {}
             Err(e) => { panic!("{:?}", e) }
         }
     }
@@ -382,12 +618,12 @@ mod tests {
     fn test_interpret4() {
         let input = "f(x)=x+1\r\n c=4\rg(x)=x+4+2*xx{3}+1";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 1);
-        functions.insert('g', 1);
+        functions.insert("f".to_string(), 1);
+        functions.insert("g".to_string(), 1);
         let mut constants = HashSet::new();
-        constants.insert('c');
+        constants.insert("c".to_string());
 
 
         let mut parser = parsers::ParserInterpret::new(input, Model{
@@ -397,7 +633,7 @@ mod tests {
         });
 
         match parser.parse() {
-            Ok(res) => {}
+            Ok(_) => {}
             Err(e) => { panic!("{:?}", e) }
         }
     }
@@ -406,10 +642,10 @@ mod tests {
     fn test_function_arity_mismatch() { //Количество переменных в интерпретации функции f не совпадает с количеством переменных в TRS
         let input = "f(x,y)=x";
         let mut variables = HashSet::new();
-        variables.insert('x');
-        variables.insert('y');
+        variables.insert("x".to_string());
+        variables.insert("y".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 1);
+        functions.insert("f".to_string(), 1);
         let mut parser = parsers::ParserInterpret::new(input, Model{
             variables,
             constants: Default::default(),
@@ -417,7 +653,7 @@ mod tests {
         });
 
         match parser.parse() {
-            Ok(res) => { panic!("должна вернуться ошибка") }
+            Ok(_) => { panic!("должна вернуться ошибка") }
             Err(e) => {println!("{:?}", e)}
         }
     }
@@ -426,9 +662,9 @@ mod tests {
     fn test_argument_repeat_interpretation() {
         let input = "f(x,x)=x";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 2);
+        functions.insert("f".to_string(), 2);
         let mut parser = parsers::ParserInterpret::new(input, Model{
             variables,
             constants: Default::default(),
@@ -436,7 +672,7 @@ mod tests {
         });
 
         match parser.parse() {
-            Ok(res) => { panic!("должна вернуться ошибка") }
+            Ok(_) => { panic!("должна вернуться ошибка") }
             Err(e) => {println!("{:?}", e)}
         }
     }
@@ -445,9 +681,9 @@ mod tests {
     fn test_interpret6() { //Переменная z не указана в качестве аргумента функции
         let input = "f(x)=z";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 1);
+        functions.insert("f".to_string(), 1);
 
 
         let mut parser = parsers::ParserInterpret::new(input, Model{
@@ -457,7 +693,7 @@ mod tests {
         });
 
         match parser.parse() {
-            Ok(res) => { panic!("должна вернуться ошибка") }
+            Ok(_) => { panic!("должна вернуться ошибка") }
             Err(e) => {println!("{:?}", e)}
         }
     }
@@ -466,9 +702,9 @@ mod tests {
     fn test_interpret7() {
         let input = "f(z)=z";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 1);
+        functions.insert("f".to_string(), 1);
 
         let mut parser = parsers::ParserInterpret::new(input, Model{
             variables,
@@ -477,7 +713,7 @@ mod tests {
         });
 
         match parser.parse() {
-            Ok(res) => {}
+            Ok(_) => {}
             Err(e) => { panic!("{:?}", e) }
         }
     }
@@ -486,9 +722,9 @@ mod tests {
     fn test_interpret8() {
         let input = "f(x, y)=2*xyx";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 2);
+        functions.insert("f".to_string(), 2);
 
         let mut parser = parsers::ParserInterpret::new(input, Model{
             variables,
@@ -506,9 +742,9 @@ mod tests {
     fn test_interpret9() {
         let input = "f(x, y)=2*xyx+4+2+x{3}xy4*xy3*x";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 2);
+        functions.insert("f".to_string(), 2);
 
         let mut parser = parsers::ParserInterpret::new(input, Model{
             variables,
@@ -522,15 +758,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_interpret_polynomial() {
+        let input = "f(x, y)=2*xyx+4+2+x{3}xy4*xy3*x\nk=5";
+        let mut variables = HashSet::new();
+        variables.insert("x".to_string());
+        let mut functions = HashMap::new();
+        functions.insert("f".to_string(), 2);
+        let mut constants = HashSet::new();
+        constants.insert("k".to_string());
+
+        let mut parser = parsers::ParserInterpret::new(input, Model{
+            variables,
+            constants,
+            functions,
+        });
+
+        let interpret = match parser.parse() {
+            Ok(ParsedData::Interpret(res)) => res,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(interpret[0].variables, vec!["x", "y"]);
+        assert_eq!(interpret[0].polynomial.to_string(), "(6 + 2 * x^2 * y + 12 * x^6 * y^2)");
+        assert_eq!(interpret[1].polynomial, Polynomial::constant(5));
+        assert_eq!(interpret[1].span.start, Position { line: 2, column: 0, offset: 32 });
+        assert_eq!(interpret[1].span.end, Position { line: 2, column: 3, offset: 35 });
+    }
+
+    #[test]
+    fn test_interpret_identifiers() {
+        let input = "variables = x, ys\nadd(x, succ(ys)) = succ(add(x, ys))\nadd(x, zero) = x";
+        let input1 = "add(x, ys) = x + 2*ys\nsucc(n) = n + 1\nzero = 1\n";
+        let mut parser_trs = parsers::ParserTRS::new(input);
+        parser_trs.parse().unwrap();
+        let model = Model {
+            variables: parser_trs.variables,
+            constants: parser_trs.constants,
+            functions: parser_trs.functions,
+        };
+        let interpret = match ParserInterpret::new(input1, model).parse() {
+            Ok(ParsedData::Interpret(res)) => res,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(interpret[0].name, "add");
+        assert_eq!(interpret[0].polynomial.to_string(), "(x + 2 * ys)");
+    }
+
+    #[test]
+    fn test_interpret_juxtaposed_identifiers() {
+        let input = "f(xs, x) = 3*xsxxs + x{2}";
+        let mut functions = HashMap::new();
+        functions.insert("f".to_string(), 2);
+
+        let mut parser = parsers::ParserInterpret::new(input, Model{
+            variables: Default::default(),
+            constants: Default::default(),
+            functions,
+        });
+
+        let interpret = match parser.parse() {
+            Ok(ParsedData::Interpret(res)) => res,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(interpret[0].polynomial.to_string(), "(3 * x * xs^2 + x^2)");
+    }
+
     #[test]
     fn test_interpret10() {
         let input = "f(x, y)=xy\nk=5";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 2);
+        functions.insert("f".to_string(), 2);
         let mut constants = HashSet::new();
-        constants.insert('k');
+        constants.insert("k".to_string());
 
         let mut parser = parsers::ParserInterpret::new(input, Model{
             variables,
@@ -548,11 +849,11 @@ mod tests {
     fn test_interpret11() {
         let input = "f(x, y)=xy\n f(x,y)=4\nk=1\nk=2";
         let mut variables = HashSet::new();
-        variables.insert('x');
+        variables.insert("x".to_string());
         let mut functions = HashMap::new();
-        functions.insert('f', 2);
+        functions.insert("f".to_string(), 2);
         let mut constants = HashSet::new();
-        constants.insert('k');
+        constants.insert("k".to_string());
 
         let mut parser = parsers::ParserInterpret::new(input, Model{
             variables,
@@ -561,7 +862,7 @@ mod tests {
         });
 
         match parser.parse() {
-            Ok(res) => {panic!("должна быть ошибка")}
+            Ok(_) => {panic!("должна быть ошибка")}
             Err(e) => {  println!("{:?}", e)}
         }
     }
@@ -580,7 +881,7 @@ mod tests {
         let mut parser_interpret = ParserInterpret::new(input1, model);
         let res = parser_interpret.parse();
         match res {
-            Ok(res) => {}
+            Ok(_) => {}
             Err(e) => { panic!("{:?}", e) }
         }
     }
@@ -589,10 +890,7 @@ mod tests {
     fn test_parse_eol() {
         let input = "variables = x,y\nf(x,h(y))=h(f(x,y))\n\ng = f";
         let mut parser_trs = parsers::ParserTRS::new(input);
-        match parser_trs.parse() {
-            Ok(res) => { panic!("должна вернуться ошибка") }
-            Err(e) => {}
-        }
+        assert!(parser_trs.parse().is_err(), "должна вернуться ошибка");
     }
 
     #[test]
@@ -605,13 +903,28 @@ mod tests {
         assert_eq!(resp.status_code, 200);
     }
 
+    #[test]
+    fn test_complete_error_json() {
+        let headers: Vec<(String, String)> = vec![("Content-Type".to_string(), "application/json".to_string())];
+        let string = "{\"TRS\":\"variables = x\\nf(x) = g(f)\", \"Interpretation\":\"\"}";
+
+        let req = rouille::Request::fake_http("GET", "", headers, Vec::from(string));
+        let resp = server::handlers::handle_request(&req);
+        assert_eq!(resp.status_code, 400);
+        let (mut reader, _) = resp.data.into_reader_and_size();
+        let mut body = String::new();
+        reader.read_to_string(&mut body).unwrap();
+        assert!(body.contains("\"kind\":\"type_mismatch\""), "{}", body);
+        assert!(body.contains("\"fatal\":true"), "{}", body);
+    }
+
     #[test]
     fn test_left_rule_missing_variable_existing_in_right_rule() {
         let input = "variables = x,y\nf(y) = f(x)";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => { panic!("должна вернуться ошибка") }
-            Err(e) => { assert_eq!(e[0], "Ошибка в строке 2, следующие переменные входят в правую часть, но не входят в левую: x") }
+            Ok(_) => { panic!("должна вернуться ошибка") }
+            Err(e) => { assert_eq!(e[0].message(), "Ошибка в строке 2, следующие переменные входят в правую часть, но не входят в левую: x") }
         }
     }
 
@@ -620,7 +933,7 @@ mod tests {
         let input = "variables = x,y\nf(x,y) = g(x)";
         let mut parser_trs = parsers::ParserTRS::new(input);
         match parser_trs.parse() {
-            Ok(res) => { }
+            Ok(_) => { }
             Err(e) => { panic!("{:?}", e) }
         }
     }
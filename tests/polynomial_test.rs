@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use tofl_gpt_parser::models::Polynomial;
+
+    #[test]
+    fn test_polynomial_normalization() {
+        let x = Polynomial::variable("x");
+        let y = Polynomial::variable("y");
+        let sum = &(&x + &y) + &x;
+        assert_eq!(sum.to_string(), "(2 * x + y)");
+        assert_eq!(&sum + &Polynomial::constant(0), sum);
+    }
+
+    #[test]
+    fn test_polynomial_pow() {
+        let x = Polynomial::variable("x");
+        let p = (&x + &Polynomial::constant(1)).pow(2);
+        assert_eq!(p.to_string(), "(1 + 2 * x + x^2)");
+    }
+
+    #[test]
+    fn test_polynomial_compose() {
+        // f(x, y) = 2*x*y + 1, подставляем x := y + 1, y := 3
+        let f = &(&Polynomial::constant(2) * &(&Polynomial::variable("x") * &Polynomial::variable("y")))
+            + &Polynomial::constant(1);
+        let mut substitution = HashMap::new();
+        substitution.insert("x".to_string(), &Polynomial::variable("y") + &Polynomial::constant(1));
+        substitution.insert("y".to_string(), Polynomial::constant(3));
+        assert_eq!(f.compose(&substitution).unwrap().to_string(), "(7 + 6 * y)");
+    }
+
+    #[test]
+    fn test_polynomial_evaluate() {
+        let p = &Polynomial::variable("x").pow(3) + &Polynomial::constant(4);
+        let mut values = HashMap::new();
+        values.insert("x".to_string(), 2);
+        assert_eq!(p.evaluate(&values), Some(12));
+        assert_eq!(p.evaluate(&HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_polynomial_big_coefficients() {
+        let p = Polynomial::constant(i64::MAX).pow(2);
+        assert_eq!(p.to_string(), "(85070591730234615847396907784232501249)");
+        assert_eq!(p.evaluate(&HashMap::new()), None);
+        assert_eq!(serde_json::to_string(&p.monomials[0]).unwrap(), "{\"coefficient\":\"85070591730234615847396907784232501249\",\"variables\":{}}");
+        assert_eq!(serde_json::to_string(&Polynomial::constant(3).monomials[0]).unwrap(), "{\"coefficient\":3,\"variables\":{}}");
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::data_structures::Term;
+    use tofl_gpt_parser::models::{ParsedData, ParsedDataTRS};
+    use tofl_gpt_parser::parsers::{Parse, ParserTRS};
+    use tofl_gpt_parser::rewriting::{match_term, Redex, Rewriter, Strategy};
+
+    fn parse(input: &str) -> ParsedDataTRS {
+        match ParserTRS::new(input).parse() {
+            Ok(ParsedData::TRS(trs)) => trs,
+            other => panic!("{:?}", other),
+        }
+    }
+
+    fn t(value: &str, childs: Vec<Term>) -> Term {
+        Term::new(value, childs)
+    }
+
+    fn num(n: usize) -> Term {
+        (0..n).fold(t("zero", vec![]), |acc, _| t("s", vec![acc]))
+    }
+
+    const ADD: &str = "variables = x, y\nadd(x, zero) = x\nadd(x, s(y)) = s(add(x, y))";
+
+    #[test]
+    fn test_matching() {
+        let trs = parse(ADD);
+        let term = t("add", vec![num(1), num(2)]);
+        let substitution = match_term(&trs.rules[1].left, &term, &trs.variables).unwrap();
+        assert_eq!(substitution["x"], num(1));
+        assert_eq!(substitution["y"], num(1));
+        assert!(match_term(&trs.rules[0].left, &term, &trs.variables).is_none());
+    }
+
+    #[test]
+    fn test_rewrite_at() {
+        let trs = parse(ADD);
+        let rewriter = Rewriter::new(&trs);
+        let term = t("s", vec![t("add", vec![num(0), num(0)])]);
+        assert_eq!(rewriter.rewrite_at(&term, &[0], 0), Some(num(1)));
+        assert_eq!(rewriter.rewrite_at(&term, &[], 0), None);
+        assert_eq!(rewriter.rewrite_at(&term, &[0], 1), None);
+    }
+
+    #[test]
+    fn test_normal_form_strategies() {
+        let trs = parse(ADD);
+        let rewriter = Rewriter::new(&trs);
+        let term = t("add", vec![num(1), t("add", vec![num(1), num(1)])]);
+        for strategy in [Strategy::LeftmostInnermost, Strategy::LeftmostOutermost, Strategy::ParallelOutermost] {
+            let reduction = rewriter.normalize(&term, strategy, 100);
+            assert!(reduction.normal_form);
+            assert_eq!(reduction.result(), &num(3), "{:?}", strategy);
+        }
+
+        let innermost = rewriter.normalize(&term, Strategy::LeftmostInnermost, 100);
+        assert_eq!(innermost.steps[0].redexes, vec![Redex { rule: 1, position: vec![1] }]);
+        assert_eq!(innermost.steps[0].term.to_string(), "add(s(zero), s(add(s(zero), zero)))");
+    }
+
+    #[test]
+    fn test_parallel_outermost() {
+        let trs = parse("variables = x\nf(a, x) = x\na = b");
+        let rewriter = Rewriter::new(&trs);
+        let term = t("g", vec![t("a", vec![]), t("a", vec![])]);
+        let step = rewriter.step(&term, Strategy::ParallelOutermost).unwrap();
+        assert_eq!(step.redexes.len(), 2);
+        assert_eq!(step.term.to_string(), "g(b, b)");
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let trs = parse("variables = x\nf(x) = g(f(x))");
+        let rewriter = Rewriter::new(&trs);
+        let reduction = rewriter.normalize(&t("f", vec![t("c", vec![])]), Strategy::LeftmostOutermost, 5);
+        assert_eq!(reduction.steps.len(), 5);
+        assert!(!reduction.normal_form);
+        assert_eq!(reduction.result().to_string(), "g(g(g(g(g(f(c))))))");
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use tofl_gpt_parser::models::ParsedData;
+    use tofl_gpt_parser::parsers::{Parse, ParserInterpret, TrsFormat};
+    use tofl_gpt_parser::server;
+    use tofl_gpt_parser::server::handlers::parse_trs;
+    use tofl_gpt_parser::termination::{find_interpretation, interpretation_to_string, PolynomialOrder, SearchOptions, Template, Verdict};
+
+    const ADD: &str = "variables = x, y\nadd(x, zero) = x\nadd(x, s(y)) = s(add(x, y))";
+
+    #[test]
+    fn test_search_linear() {
+        let (trs, model) = parse_trs(ADD, TrsFormat::Course).unwrap();
+        let found = find_interpretation(&trs, &SearchOptions::default()).unwrap().unwrap();
+        let verdicts = PolynomialOrder::new(&found).check(&trs).unwrap();
+        assert!(verdicts.iter().all(|v| v.verdict == Verdict::Proved));
+
+        // Текст интерпретации разбирается обратно тем же парсером
+        let text = interpretation_to_string(&found);
+        assert_eq!(text, "add(x1, x2) = 2*x2 + x1\ns(x1) = x1 + 1\nzero = 1\n");
+        match ParserInterpret::new(&text, model).parse() {
+            Ok(ParsedData::Interpret(parsed)) => assert_eq!(parsed.len(), 3),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_search_quadratic() {
+        let (trs, _) = parse_trs("variables = x\nf(g(x)) = g(g(f(x)))", TrsFormat::Course).unwrap();
+        let options = SearchOptions { template: Template::Quadratic, ..SearchOptions::default() };
+        let found = find_interpretation(&trs, &options).unwrap().unwrap();
+        let verdicts = PolynomialOrder::new(&found).check(&trs).unwrap();
+        assert_eq!(verdicts[0].verdict, Verdict::Proved);
+    }
+
+    #[test]
+    fn test_search_not_found() {
+        let (trs, _) = parse_trs("variables = x\nf(x) = g(f(x))", TrsFormat::Course).unwrap();
+        assert!(find_interpretation(&trs, &SearchOptions::default()).unwrap().is_none());
+
+        let (trs, _) = parse_trs(ADD, TrsFormat::Course).unwrap();
+        let options = SearchOptions { max_steps: 5, ..SearchOptions::default() };
+        assert_eq!(find_interpretation(&trs, &options).unwrap_err(),
+                   "Поиск интерпретации прерван: превышен лимит в 5 шагов");
+    }
+
+    #[test]
+    fn test_search_endpoint() {
+        let headers: Vec<(String, String)> = vec![("Content-Type".to_string(), "application/json".to_string())];
+        let string = "{\"TRS\":\"variables = x\\nf(g(x)) = g(x)\", \"MaxCoefficient\": 2}";
+        let req = rouille::Request::fake_http("POST", "/search", headers, Vec::from(string));
+        let resp = server::handlers::handle_search(&req);
+        assert_eq!(resp.status_code, 200);
+        let (mut reader, _) = resp.data.into_reader_and_size();
+        let mut body = String::new();
+        reader.read_to_string(&mut body).unwrap();
+        assert!(body.starts_with("{\"found\":true,\"json_interpret\":{\"functions\":["), "{}", body);
+        assert!(body.contains("\"interpretation\":\"f(x1) = 2*x1\\ng(x1) = x1 + 1\\n\""), "{}", body);
+    }
+}
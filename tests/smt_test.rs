@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use tofl_gpt_parser::models::ParsedData;
+    use tofl_gpt_parser::parsers::{Parse, ParserInterpret, TrsFormat};
+    use tofl_gpt_parser::server::handlers::parse_trs;
+    use tofl_gpt_parser::termination::SmtEncoding;
+
+    const ADD: &str = "variables = x, y\nadd(x, zero) = x\nadd(x, s(y)) = s(add(x, y))";
+
+    fn encode(trs: &str, interpretation: &str) -> SmtEncoding {
+        let (trs, model) = parse_trs(trs, TrsFormat::Course).unwrap();
+        let known = match ParserInterpret::partial(interpretation, model).parse() {
+            Ok(ParsedData::Interpret(interpret)) => interpret,
+            other => panic!("{:?}", other),
+        };
+        SmtEncoding::new(&trs, &known).unwrap()
+    }
+
+    fn satisfied(encoding: &SmtEncoding, values: &[(&str, i64)]) -> bool {
+        let values: HashMap<String, i64> = values.iter().map(|(k, v)| (k.to_string(), *v)).collect();
+        encoding.constraints.iter().all(|c| c.expression.evaluate(&values).unwrap() >= c.bound)
+    }
+
+    #[test]
+    fn test_smt_templates() {
+        let encoding = encode(ADD, "zero = 1");
+        assert_eq!(encoding.unknowns, vec!["c_add_0", "c_add_1", "c_add_2", "c_s_0", "c_s_1"]);
+
+        // add(x1, x2) = x1 + 2*x2, s(x1) = x1 + 1
+        let solution = [("c_add_0", 0), ("c_add_1", 1), ("c_add_2", 2), ("c_s_0", 1), ("c_s_1", 1)];
+        assert!(satisfied(&encoding, &solution));
+        let wrong = [("c_add_0", 0), ("c_add_1", 1), ("c_add_2", 1), ("c_s_0", 1), ("c_s_1", 1)];
+        assert!(!satisfied(&encoding, &wrong));
+
+        let script = encoding.to_smt_lib();
+        assert!(script.starts_with("; Условия завершаемости TRS для полиномиальной интерпретации\n(set-logic QF_NIA)\n"));
+        assert!(script.contains("(declare-fun c_s_1 () Int)\n"));
+        assert!(script.contains("; zero = (1)\n"));
+        assert!(script.contains("; монотонность add\n(assert (>= c_add_1 1))\n(assert (>= c_add_2 1))\n"));
+        assert!(script.contains("; правило 0: add(x, zero) = x\n(assert (>= (+ c_add_0 c_add_2) 1))\n(assert (>= (+ (- 1) c_add_1) 0))\n"));
+        assert!(script.ends_with("(check-sat)\n(get-model)\n"));
+    }
+
+    #[test]
+    fn test_smt_known_interpretation() {
+        let encoding = encode("variables = x\nf(x) = g(x)", "f(x) = 2*x + 1\ng(x) = 2*x + 1");
+        assert!(encoding.unknowns.is_empty());
+        let script = encoding.to_smt_lib();
+        assert!(!script.contains("declare-fun"));
+        // Монотонность выполнена и опущена, свободный член разности равен 0
+        assert!(!script.contains("монотонность"));
+        assert!(script.contains("; правило 0: f(x) = g(x)\n(assert (>= 0 1))\n"));
+    }
+
+    #[test]
+    fn test_smt_symbols() {
+        let (trs, _) = parse_trs("(VAR x)\n(RULES +(x, 0) -> f'(x))", TrsFormat::Tpdb).unwrap();
+        let script = SmtEncoding::new(&trs, &Vec::new()).unwrap().to_smt_lib();
+        assert!(script.contains("(declare-fun c_+_0 () Int)\n"));
+        assert!(script.contains("(declare-fun |c_f'_1| () Int)\n"));
+        assert!(script.contains("; +(x1, x2) = (c_+_0 + c_+_1 * x1 + c_+_2 * x2)\n"));
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use tofl_gpt_parser::models::data_structures::Model;
+    use tofl_gpt_parser::models::{ParsedData, ParsedDataInterpret, ParsedDataTRS};
+    use tofl_gpt_parser::parsers::{Parse, ParserInterpret, ParserTRS, TrsFormat};
+    use tofl_gpt_parser::server::handlers::process;
+    use tofl_gpt_parser::termination::{PolynomialOrder, Verdict, DEFAULT_MINIMUM};
+
+    fn parse(trs: &str, interpretation: &str) -> (ParsedDataTRS, ParsedDataInterpret) {
+        let mut parser_trs = ParserTRS::new(trs);
+        let trs = match parser_trs.parse() {
+            Ok(ParsedData::TRS(trs)) => trs,
+            other => panic!("{:?}", other),
+        };
+        let model = Model {
+            variables: parser_trs.variables,
+            constants: parser_trs.constants,
+            functions: parser_trs.functions,
+        };
+        let interpret = match ParserInterpret::new(interpretation, model).parse() {
+            Ok(ParsedData::Interpret(interpret)) => interpret,
+            other => panic!("{:?}", other),
+        };
+        (trs, interpret)
+    }
+
+    #[test]
+    fn test_termination_proved() {
+        let (trs, interpret) = parse("variables = x\nf(g(x)) = g(f(x))\nf(c) = c", "f(x) = 2*x+1\ng(x) = x+1\nc = 1");
+        let verdicts = PolynomialOrder::new(&interpret).check(&trs).unwrap();
+        assert_eq!(verdicts.len(), 2);
+        assert_eq!(verdicts[0].left.to_string(), "(3 + 2 * x)");
+        assert_eq!(verdicts[0].right.to_string(), "(2 + 2 * x)");
+        assert!(verdicts.iter().all(|v| v.verdict == Verdict::Proved));
+    }
+
+    #[test]
+    fn test_termination_counterexample() {
+        let (trs, interpret) = parse("variables = x,y\nf(x,y) = g(y)", "f(x,y) = x+y\ng(y) = 2*y");
+        let verdicts = PolynomialOrder::new(&interpret).check(&trs).unwrap();
+        match &verdicts[0].verdict {
+            Verdict::Counterexample { left, right, .. } => assert!(left <= right),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_termination_not_proved() {
+        // x^2 + 2 > 2x верно для всех x, но не выводится покоэффициентным сравнением
+        let (trs, interpret) = parse("variables = x\nf(x) = g(x)", "f(x) = x{2}+2\ng(x) = 2*x");
+        let verdicts = PolynomialOrder::new(&interpret).check(&trs).unwrap();
+        assert_eq!(verdicts[0].difference.to_string(), "(2 - 2 * x + x^2)");
+        assert_eq!(verdicts[0].verdict, Verdict::NotProved);
+    }
+
+    #[test]
+    fn test_termination_minimum() {
+        // 3x > x + 1 при x >= 1, но не при x = 0
+        let (trs, interpret) = parse("variables = x\nf(x) = g(x)", "f(x) = 3*x\ng(x) = x + 1");
+        let verdicts = PolynomialOrder::new(&interpret).check(&trs).unwrap();
+        match &verdicts[0].verdict {
+            Verdict::Counterexample { assignment, .. } => assert_eq!(assignment["x"], 0),
+            other => panic!("{:?}", other),
+        }
+        let verdicts = PolynomialOrder::new(&interpret).with_minimum(1).check(&trs).unwrap();
+        assert_eq!(verdicts[0].verdict, Verdict::NotProved);
+
+        let res = process("variables = x\nf(x) = g(x)", TrsFormat::Course, "f(x) = 3*x\ng(x) = x + 1", DEFAULT_MINIMUM, false).ok().unwrap();
+        assert!(res.diagnostics.is_empty());
+        assert_eq!(res.termination[0].verdict, Verdict::NotProved);
+    }
+}